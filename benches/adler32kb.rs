@@ -7,8 +7,8 @@ use hash::{adler32, Hash};
 fn benchmark(c: &mut Criterion) {
     let data = {
         let mut v = [0u8; 1024];
-        for i in 0..v.len() {
-            v[i] = i as u8;
+        for (i, x) in v.iter_mut().enumerate() {
+            *x = i as u8;
         }
         v
     };
@@ -28,7 +28,7 @@ fn benchmark(c: &mut Criterion) {
     grp.bench_function("adler32kb", |b| {
         b.iter_batched(
             || Some(input.clone()),
-            |v| f(v),
+            &mut f,
             criterion::BatchSize::SmallInput,
         );
     });
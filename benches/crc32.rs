@@ -5,6 +5,9 @@ use hash::Hash32;
 
 fn benchmark(c: &mut Criterion) {
     benchmark_all(c, "poly=IEEE", crc32::new_ieee);
+    benchmark_all(c, "poly=Castagnoli", || {
+        crc32::new(crc32::make_table(crc32::CASTAGNOLI))
+    });
 }
 
 fn benchmark_all<H>(c: &mut Criterion, name: &str, new_hash: fn() -> H)
@@ -36,8 +39,8 @@ where
     let setup = || {
         let data = vec![0u8; n + alignment];
         let mut data = data[alignment..].to_vec();
-        for i in 0..data.len() {
-            data[i] = i as u8;
+        for (i, v) in data.iter_mut().enumerate() {
+            *v = i as u8;
         }
 
         let mut h = new_hash();
@@ -46,7 +49,7 @@ where
 
         // warm up
         h.reset();
-        let _ = h.write(&data);
+        h.update(&data);
         h.sum(Some(input.clone()));
 
         (h, data, Some(input))
@@ -56,7 +59,7 @@ where
         let mut h = h;
 
         h.reset();
-        let _ = h.write(&data);
+        h.update(&data);
         h.sum(input);
     };
 
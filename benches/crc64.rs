@@ -0,0 +1,39 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Bencher, Criterion, Throughput};
+
+use hash::crc64::{self, Table};
+
+fn benchmark(c: &mut Criterion) {
+    benchmark_all(c, "poly=ISO", crc64::make_table(crc64::ISO));
+    benchmark_all(c, "poly=ECMA", crc64::make_table(crc64::ECMA));
+}
+
+fn benchmark_all(c: &mut Criterion, name: &str, table: Table) {
+    for size in [15usize, 40, 512, 1 << 10, 4 << 10, 32 << 10] {
+        let sub = if size < 1024 {
+            size.to_string()
+        } else {
+            format!("{}kB", size >> 10)
+        };
+
+        let mut grp = c.benchmark_group(format!("{name}/size={sub}"));
+        grp.throughput(Throughput::Bytes(size as u64));
+
+        grp.bench_function("update", |b| bench(b, &table, size));
+    }
+}
+
+fn bench(b: &mut Bencher, table: &Table, n: usize) {
+    let mut data = vec![0u8; n];
+    for (i, v) in data.iter_mut().enumerate() {
+        *v = i as u8;
+    }
+
+    b.iter_batched(
+        || data.clone(),
+        |data| crc64::update(0, table, &data),
+        BatchSize::SmallInput,
+    );
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
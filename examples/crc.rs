@@ -0,0 +1,14 @@
+use hash::crc::{Crc, CRC_32_BZIP2};
+use hash::{Hash, Hash32};
+
+fn main() {
+    let bzip2 = Crc::new(CRC_32_BZIP2);
+
+    assert_eq!(0xfc891918, bzip2.checksum(b"123456789"));
+
+    let mut h = bzip2.digest();
+    h.update(b"12345");
+    h.update(b"6789");
+
+    assert_eq!(0xfc891918, h.sum32());
+}
@@ -0,0 +1,14 @@
+use hash::crc32;
+
+fn main() {
+    let (a, b) = (b"Hello ".as_slice(), b"world".as_slice());
+
+    let crc1 = crc32::checksum_ieee(a);
+    let crc2 = crc32::checksum_ieee(b);
+
+    let expect = crc32::checksum_ieee(b"Hello world");
+
+    let got = crc32::combine(crc1, crc2, b.len() as u64, crc32::IEEE);
+
+    assert_eq!(expect, got);
+}
@@ -1,11 +1,14 @@
 use hash::crc32;
 
-fn main() {
-    let crc32q = crc32::make_table(0xD5828281);
+// The table is built at compile time.
+static CRC32Q: crc32::Table = crc32::Table::from_poly(0xD5828281);
 
+fn main() {
     let expect: u32 = 0x2964d064;
 
-    let got = crc32::checksum(b"Hello world", &crc32q);
-
+    let got = crc32::checksum(b"Hello world", &CRC32Q);
     assert_eq!(expect, got);
+
+    let t = crc32::make_table(0xD5828281);
+    assert_eq!(expect, crc32::checksum(b"Hello world", &t));
 }
@@ -0,0 +1,11 @@
+use hash::crc32;
+
+fn main() {
+    let t = crc32::make_table_normal(crc32::IEEE_NORMAL);
+
+    let bzip2 = crc32::checksum(b"123456789", &t);
+    assert_eq!(0xfc891918, bzip2);
+
+    let mpeg2 = !bzip2;
+    assert_eq!(0x0376e6e7, mpeg2);
+}
@@ -7,6 +7,8 @@ fn main() {
     let table = crc64::make_table(crc64::ISO);
 
     let got = crc64::checksum(msg.as_bytes(), &table);
+    assert_eq!(expect, got);
 
+    let got = crc64::checksum(msg.as_bytes(), &crc64::ISO_TABLE);
     assert_eq!(expect, got);
 }
@@ -0,0 +1,16 @@
+use hash::crc64;
+
+fn main() {
+    let table = crc64::make_table(crc64::ECMA);
+
+    let (a, b) = (b"hello ".as_slice(), b"world".as_slice());
+
+    let crc1 = crc64::checksum(a, &table);
+    let crc2 = crc64::checksum(b, &table);
+
+    let expect = crc64::checksum(b"hello world", &table);
+
+    let got = crc64::combine(crc1, crc2, b.len() as u64, crc64::ECMA);
+
+    assert_eq!(expect, got);
+}
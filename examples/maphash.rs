@@ -8,7 +8,7 @@ fn main() {
     h.write_string("hello, ").unwrap();
     println!("{:#016x}", h.sum64());
 
-    h.write(&[b'w', b'o', b'r', b'l', b'd']).unwrap();
+    h.write_all(b"world").unwrap();
     println!("{:#016x}", h.sum64());
 
     h.reset();
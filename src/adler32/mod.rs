@@ -12,9 +12,9 @@
 //! [RFC 1950]: https://rfc-editor.org/rfc/rfc1950.html
 //!
 
-use std::io::Write;
+use alloc::vec::Vec;
 
-use crate::{Hash, Hash32};
+use crate::{BinaryMarshaler, BinaryUnmarshaler, Error, Hash, Hash32};
 
 /// The size of an Adler-32 checksum in bytes.
 pub const SIZE: usize = 4;
@@ -27,20 +27,54 @@ const MODULO: u32 = 65521;
 /// It is mentioned in RFC 1950 (search for "5552").
 const NMAX: usize = 5552;
 
+const MAGIC: &[u8] = b"adl\x01";
+const MARSHALED_SIZE: usize = MAGIC.len() + 4;
+
 /// checksum returns the Adler-32 checksum of data.
 pub fn checksum(data: &[u8]) -> u32 {
     update(1, data)
 }
 
+/// combine returns the Adler-32 checksum of the concatenation A||B, given adler1 of A, adler2 of B and the length
+/// of B in bytes.
+///
+/// It follows the semantics of zlib's `adler32_combine`.
+pub fn combine(adler1: u32, adler2: u32, len2: u64) -> u32 {
+    let rem = (len2 % (MODULO as u64)) as u32;
+
+    let mut s1 = adler1 & 0xffff;
+    let mut s2 = (rem * s1) % MODULO;
+
+    s1 += (adler2 & 0xffff) + MODULO - 1;
+    s2 += (adler1 >> 16) + (adler2 >> 16) + MODULO - rem;
+
+    if s1 >= MODULO {
+        s1 -= MODULO;
+    }
+    if s1 >= MODULO {
+        s1 -= MODULO;
+    }
+    if s2 >= (MODULO << 1) {
+        s2 -= MODULO << 1;
+    }
+    if s2 >= MODULO {
+        s2 -= MODULO;
+    }
+
+    (s2 << 16) | s1
+}
+
 /// new returns a new hash.Hash32 computing the Adler-32 checksum. Its [sum][crate::Hash::sum] method will lay the
-/// value out in big-endian byte order.
-pub fn new() -> impl Hash32 {
+/// value out in big-endian byte order. The returned Hash32 also implements [BinaryMarshaler] and
+/// [BinaryUnmarshaler] to marshal and unmarshal the internal state of the hash.
+pub fn new() -> Digest {
     Digest::new()
 }
 
-/// digest represents the partial evaluation of a checksum.
+/// Digest represents the partial evaluation of a checksum, as returned by [new].
 /// The low 16 bits are s1, the high 16 bits are s2.
-struct Digest(u32);
+#[derive(Clone, Debug)]
+pub struct Digest(u32);
 
 impl Digest {
     fn new() -> Self {
@@ -50,16 +84,20 @@ impl Digest {
     }
 }
 
+impl Default for Digest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Hash for Digest {
-    fn sum(&mut self, b: Option<Vec<u8>>) -> Vec<u8> {
+    fn update(&mut self, p: &[u8]) {
+        self.0 = update(self.0, p);
+    }
+
+    fn sum_into(&self, out: &mut [u8]) {
         let s = self.0.to_be_bytes();
-        match b {
-            Some(mut v) => {
-                v.extend_from_slice(&s);
-                v
-            }
-            None => s.to_vec(),
-        }
+        out[..s.len()].copy_from_slice(&s);
     }
 
     fn reset(&mut self) {
@@ -76,27 +114,42 @@ impl Hash for Digest {
 }
 
 impl Hash32 for Digest {
-    fn sum32(&mut self) -> u32 {
+    fn sum32(&self) -> u32 {
         self.0
     }
 }
 
-impl Write for Digest {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.0 = update(self.0, buf);
-        Ok(buf.len())
+impl BinaryMarshaler for Digest {
+    fn marshal_binary(&self) -> Result<Vec<u8>, Error> {
+        let mut b = Vec::with_capacity(MARSHALED_SIZE);
+        b.extend_from_slice(MAGIC);
+        b.extend_from_slice(&self.0.to_be_bytes());
+        Ok(b)
     }
+}
 
-    fn flush(&mut self) -> std::io::Result<()> {
+impl BinaryUnmarshaler for Digest {
+    fn unmarshal_binary(&mut self, data: &[u8]) -> Result<(), Error> {
+        if !data.starts_with(MAGIC) {
+            return Err(Error::InvalidStateIdentifier);
+        }
+        if data.len() != MARSHALED_SIZE {
+            return Err(Error::InvalidStateSize);
+        }
+
+        let s: [u8; 4] = data[MAGIC.len()..].try_into().expect("state as [u8; 4]");
+        self.0 = u32::from_be_bytes(s);
         Ok(())
     }
 }
 
+crate::impl_write!(Digest);
+
 fn update(d: u32, p: &[u8]) -> u32 {
     let (mut s1, mut s2) = (d & 0xffff, d >> 16);
 
     let mut p = p;
-    while p.len() > 0 {
+    while !p.is_empty() {
         let mut q: &[u8] = &[];
 
         if p.len() > NMAX {
@@ -1,5 +1,35 @@
 use super::*;
 
+use crate::{BinaryMarshaler, BinaryUnmarshaler};
+
+#[test]
+fn combine() {
+    for (i, g) in GOLDEN_TEST_VECTOR.iter().enumerate() {
+        for j in [0, 1, g.input.len() / 3, g.input.len() / 2, g.input.len()] {
+            let (a, b) = g.input.split_at(j.min(g.input.len()));
+
+            let got = super::combine(checksum(a), checksum(b), b.len() as u64);
+            assert_eq!(
+                g.out, got,
+                "#{i} split at {j}: expect 0x{:08x}, got 0x{:08x}",
+                g.out, got
+            );
+        }
+    }
+}
+
+#[test]
+fn digest() {
+    let mut h = Digest::default();
+    h.update(b"hello");
+    assert_eq!(checksum(b"hello"), h.sum32());
+
+    let mut h2 = h.clone();
+    h2.update(b" world");
+    assert_eq!(checksum(b"hello"), h.sum32(), "clone shares the state");
+    assert_eq!(checksum(b"hello world"), h2.sum32());
+}
+
 #[test]
 fn golden() {
     for (i, g) in GOLDEN_TEST_VECTOR.iter().enumerate() {
@@ -16,9 +46,63 @@ fn golden() {
             "optimized implementation: #{i} expect 0x{:08x}, got 0x{:08x}",
             g.out, got
         );
+
+        let mut h = new();
+        h.update(&g.input);
+        let got = h.sum32();
+        assert_eq!(
+            g.out, got,
+            "Hash::update: #{i} expect 0x{:08x}, got 0x{:08x}",
+            g.out, got
+        );
     }
 }
 
+#[test]
+fn golden_marshal() {
+    for (i, g) in GOLDEN_TEST_VECTOR.iter().enumerate() {
+        let (head, tail) = g.input.split_at(g.input.len() / 2);
+
+        let mut h = new();
+        let mut h2 = new();
+
+        h.update(head);
+
+        let state = h.marshal_binary().expect("marshal_binary");
+        let expect = [MAGIC, &checksum(head).to_be_bytes()].concat();
+        assert_eq!(expect, state, "#{i} checksum(half) state mismatch");
+
+        h2.unmarshal_binary(&state).expect("unmarshal_binary");
+
+        h.update(tail);
+        h2.update(tail);
+
+        assert_eq!(
+            h.sum32(),
+            h2.sum32(),
+            "#{i} checksum(...) differs after restoring the state"
+        );
+    }
+}
+
+#[test]
+fn unmarshal_bad_state() {
+    let mut h = new();
+
+    assert_eq!(
+        Err(crate::Error::InvalidStateIdentifier),
+        h.unmarshal_binary(b"crc\x01\x00\x00\x00\x01")
+    );
+    assert_eq!(
+        Err(crate::Error::InvalidStateIdentifier),
+        h.unmarshal_binary(b"adl")
+    );
+    assert_eq!(
+        Err(crate::Error::InvalidStateSize),
+        h.unmarshal_binary(b"adl\x01\x00\x00\x01")
+    );
+}
+
 struct Test {
     out: u32,
     input: Vec<u8>,
@@ -86,7 +170,7 @@ lazy_static::lazy_static! {
 
 fn build_input(s: &[u8], n: usize, suffix: Option<&str>) -> Vec<u8> {
     let olen = match &suffix {
-        Some(ref v) => s.len() * n + v.len(),
+        Some(v) => s.len() * n + v.len(),
         None => s.len(),
     };
 
@@ -0,0 +1,113 @@
+use alloc::boxed::Box;
+use core::ops::Deref;
+
+use crate::crc::{Crc, Width};
+use crate::{Hash, Hash16, Hash32, Hash64, Hash8};
+
+/// Digest is the [Hash](crate::Hash) computing the CRC of a [Crc] engine incrementally.
+///
+/// It borrows the engine when returned by [Crc::digest], and owns it when returned by [Crc::into_digest].
+#[derive(Clone, Debug)]
+pub struct Digest<'a, W: Width> {
+    crc: Engine<'a, W>,
+    reg: u64,
+}
+
+/// Engine is the [Crc] engine of a digest, either borrowed or owned. An owned engine is boxed, so that the digest
+/// itself stays small.
+#[derive(Clone, Debug)]
+enum Engine<'a, W: Width> {
+    Borrowed(&'a Crc<W>),
+    Owned(Box<Crc<W>>),
+}
+
+impl<W: Width> Deref for Engine<'_, W> {
+    type Target = Crc<W>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Engine::Borrowed(crc) => crc,
+            Engine::Owned(crc) => crc,
+        }
+    }
+}
+
+impl<'a, W: Width> Digest<'a, W> {
+    pub(super) fn new(crc: &'a Crc<W>) -> Self {
+        Self::with_engine(Engine::Borrowed(crc))
+    }
+
+    pub(super) fn owned(crc: Crc<W>) -> Digest<'static, W> {
+        Digest::with_engine(Engine::Owned(Box::new(crc)))
+    }
+
+    fn with_engine(crc: Engine<'a, W>) -> Self {
+        let reg = crc.init();
+        Self { crc, reg }
+    }
+
+    /// value returns the CRC of the bytes written so far.
+    pub fn value(&self) -> W {
+        self.crc.finalize(self.reg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Width> std::io::Write for Digest<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Hash::update(self, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Width> Hash for Digest<'_, W> {
+    fn update(&mut self, p: &[u8]) {
+        self.reg = self.crc.feed(self.reg, p);
+    }
+
+    fn sum_into(&self, out: &mut [u8]) {
+        let n = self.size();
+        let s = self.value().into_u64().to_be_bytes();
+        out[..n].copy_from_slice(&s[s.len() - n..]);
+    }
+
+    fn reset(&mut self) {
+        self.reg = self.crc.init();
+    }
+
+    fn size(&self) -> usize {
+        (self.crc.algorithm.width as usize + 7) / 8
+    }
+
+    fn block_size(&self) -> isize {
+        1
+    }
+}
+
+impl Hash8 for Digest<'_, u8> {
+    fn sum8(&self) -> u8 {
+        self.value()
+    }
+}
+
+impl Hash16 for Digest<'_, u16> {
+    fn sum16(&self) -> u16 {
+        self.value()
+    }
+}
+
+impl Hash32 for Digest<'_, u32> {
+    fn sum32(&self) -> u32 {
+        self.value()
+    }
+}
+
+impl Hash64 for Digest<'_, u64> {
+    fn sum64(&self) -> u64 {
+        self.value()
+    }
+}
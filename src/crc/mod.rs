@@ -0,0 +1,489 @@
+//! Module crc implements cyclic redundancy checks of any width up to 64 bits, parameterized by the Rocksoft model
+//! as catalogued by [CRC RevEng].
+//!
+//! An [Algorithm] describes a CRC by its width, polynomial, initial value, input/output reflection and final xor
+//! value. Polynomials are given in the normal (MSB-first) representation, as in the catalogue. A [Crc] engine
+//! computes checksums of an algorithm with a precomputed table.
+//!
+//! The [crc32](crate::crc32) and [crc64](crate::crc64) modules remain the presets of the LSB-first algorithms
+//! used by Go, with hardware acceleration where available.
+//!
+//! # Example
+//! ```
+#![doc = include_str!("../../examples/crc.rs")]
+//! ```
+//!
+//! [CRC RevEng]: https://reveng.sourceforge.io/crc-catalogue/all.htm
+//!
+
+/// Width is implemented by the unsigned integers holding a CRC value, i.e. u8, u16, u32 and u64. It is sealed, since
+/// the engine computes in a 64-bit register.
+pub trait Width: Copy + Eq + core::fmt::Debug + sealed::Sealed {
+    /// The number of bits of the integer type.
+    const BITS: u8;
+
+    /// from_u64 truncates v to the width.
+    fn from_u64(v: u64) -> Self;
+
+    /// into_u64 zero-extends self into u64.
+    fn into_u64(self) -> u64;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_width {
+    ($($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl Width for $t {
+                const BITS: u8 = <$t>::BITS as u8;
+
+                fn from_u64(v: u64) -> Self {
+                    v as $t
+                }
+
+                fn into_u64(self) -> u64 {
+                    self as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_width!(u8, u16, u32, u64);
+
+/// Algorithm holds the parameters of a CRC in the Rocksoft model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Algorithm<W: Width> {
+    /// The number of bits of the CRC, which must be in 1..=W::BITS.
+    pub width: u8,
+    /// The generator polynomial in normal representation, without the leading x^width term.
+    pub poly: W,
+    /// The value of the register before processing any data, in normal representation.
+    pub init: W,
+    /// Whether each input byte is processed LSB-first.
+    pub refin: bool,
+    /// Whether the register is reflected before the final xor.
+    pub refout: bool,
+    /// The value xor-ed into the final register.
+    pub xorout: W,
+    /// The checksum of the ASCII string "123456789".
+    pub check: W,
+}
+
+/// CRC-8/AUTOSAR, specified by AUTOSAR for end-to-end protection.
+pub const CRC_8_AUTOSAR: Algorithm<u8> = Algorithm {
+    width: 8,
+    poly: 0x2f,
+    init: 0xff,
+    refin: false,
+    refout: false,
+    xorout: 0xff,
+    check: 0xdf,
+};
+
+/// CRC-8/CDMA2000.
+pub const CRC_8_CDMA2000: Algorithm<u8> = Algorithm {
+    width: 8,
+    poly: 0x9b,
+    init: 0xff,
+    refin: false,
+    refout: false,
+    xorout: 0x00,
+    check: 0xda,
+};
+
+/// CRC-8/DVB-S2.
+pub const CRC_8_DVB_S2: Algorithm<u8> = Algorithm {
+    width: 8,
+    poly: 0xd5,
+    init: 0x00,
+    refin: false,
+    refout: false,
+    xorout: 0x00,
+    check: 0xbc,
+};
+
+/// CRC-8/MAXIM-DOW, used by the Dallas/Maxim 1-Wire bus.
+pub const CRC_8_MAXIM_DOW: Algorithm<u8> = Algorithm {
+    width: 8,
+    poly: 0x31,
+    init: 0x00,
+    refin: true,
+    refout: true,
+    xorout: 0x00,
+    check: 0xa1,
+};
+
+/// CRC-8/SMBUS, used by the System Management Bus packet error checking.
+pub const CRC_8_SMBUS: Algorithm<u8> = Algorithm {
+    width: 8,
+    poly: 0x07,
+    init: 0x00,
+    refin: false,
+    refout: false,
+    xorout: 0x00,
+    check: 0xf4,
+};
+
+/// CRC-16/ARC, also known as CRC-16/IBM.
+pub const CRC_16_ARC: Algorithm<u16> = Algorithm {
+    width: 16,
+    poly: 0x8005,
+    init: 0x0000,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0xbb3d,
+};
+
+/// CRC-16/IBM-3740, also known as CRC-16/CCITT-FALSE.
+pub const CRC_16_IBM_3740: Algorithm<u16> = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0xffff,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+    check: 0x29b1,
+};
+
+/// CRC-16/IBM-SDLC, also known as CRC-16/X-25.
+pub const CRC_16_IBM_SDLC: Algorithm<u16> = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0xffff,
+    refin: true,
+    refout: true,
+    xorout: 0xffff,
+    check: 0x906e,
+};
+
+/// CRC-16/KERMIT, also known as CRC-16/CCITT.
+pub const CRC_16_KERMIT: Algorithm<u16> = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0x2189,
+};
+
+/// CRC-16/MODBUS.
+pub const CRC_16_MODBUS: Algorithm<u16> = Algorithm {
+    width: 16,
+    poly: 0x8005,
+    init: 0xffff,
+    refin: true,
+    refout: true,
+    xorout: 0x0000,
+    check: 0x4b37,
+};
+
+/// CRC-16/XMODEM.
+pub const CRC_16_XMODEM: Algorithm<u16> = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0x0000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+    check: 0x31c3,
+};
+
+/// CRC-32/BZIP2, also known as CRC-32/AAL5.
+pub const CRC_32_BZIP2: Algorithm<u32> = Algorithm {
+    width: 32,
+    poly: 0x04c11db7,
+    init: 0xffffffff,
+    refin: false,
+    refout: false,
+    xorout: 0xffffffff,
+    check: 0xfc891918,
+};
+
+/// CRC-32/CKSUM, computed by the POSIX `cksum` utility.
+pub const CRC_32_CKSUM: Algorithm<u32> = Algorithm {
+    width: 32,
+    poly: 0x04c11db7,
+    init: 0x00000000,
+    refin: false,
+    refout: false,
+    xorout: 0xffffffff,
+    check: 0x765e7680,
+};
+
+/// CRC-32/ISCSI, i.e. the one of [crc32::CASTAGNOLI](crate::crc32::CASTAGNOLI).
+pub const CRC_32_ISCSI: Algorithm<u32> = Algorithm {
+    width: 32,
+    poly: 0x1edc6f41,
+    init: 0xffffffff,
+    refin: true,
+    refout: true,
+    xorout: 0xffffffff,
+    check: 0xe3069283,
+};
+
+/// CRC-32/ISO-HDLC, i.e. the one of [crc32::IEEE](crate::crc32::IEEE).
+pub const CRC_32_ISO_HDLC: Algorithm<u32> = Algorithm {
+    width: 32,
+    poly: 0x04c11db7,
+    init: 0xffffffff,
+    refin: true,
+    refout: true,
+    xorout: 0xffffffff,
+    check: 0xcbf43926,
+};
+
+/// CRC-32/MPEG-2.
+pub const CRC_32_MPEG_2: Algorithm<u32> = Algorithm {
+    width: 32,
+    poly: 0x04c11db7,
+    init: 0xffffffff,
+    refin: false,
+    refout: false,
+    xorout: 0x00000000,
+    check: 0x0376e6e7,
+};
+
+/// CRC-64/ECMA-182.
+pub const CRC_64_ECMA_182: Algorithm<u64> = Algorithm {
+    width: 64,
+    poly: 0x42f0e1eba9ea3693,
+    init: 0x0000000000000000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000000000000000,
+    check: 0x6c40df5f0b497347,
+};
+
+/// CRC-64/GO-ISO, i.e. the one of [crc64::ISO](crate::crc64::ISO).
+pub const CRC_64_GO_ISO: Algorithm<u64> = Algorithm {
+    width: 64,
+    poly: 0x000000000000001b,
+    init: 0xffffffffffffffff,
+    refin: true,
+    refout: true,
+    xorout: 0xffffffffffffffff,
+    check: 0xb90956c775a41001,
+};
+
+/// CRC-64/WE.
+pub const CRC_64_WE: Algorithm<u64> = Algorithm {
+    width: 64,
+    poly: 0x42f0e1eba9ea3693,
+    init: 0xffffffffffffffff,
+    refin: false,
+    refout: false,
+    xorout: 0xffffffffffffffff,
+    check: 0x62ec59e3f1a4f00a,
+};
+
+/// CRC-64/XZ, i.e. the one of [crc64::ECMA](crate::crc64::ECMA).
+pub const CRC_64_XZ: Algorithm<u64> = Algorithm {
+    width: 64,
+    poly: 0x42f0e1eba9ea3693,
+    init: 0xffffffffffffffff,
+    refin: true,
+    refout: true,
+    xorout: 0xffffffffffffffff,
+    check: 0x995dc9bbdf1939fa,
+};
+
+/// Crc computes the checksums of an [Algorithm] with a precomputed 256-word table.
+///
+/// The register is kept reflected in the low bits for LSB-first algorithms, and left-aligned in 64 bits for
+/// MSB-first ones, so that every width shares the same byte-at-a-time update.
+#[derive(Clone, Copy)]
+pub struct Crc<W: Width> {
+    algorithm: Algorithm<W>,
+    table: [u64; 256],
+}
+
+impl<W: Width> core::fmt::Debug for Crc<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Crc")
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+// Engines of the same algorithm always have identical tables, so comparing algorithms is enough.
+impl<W: Width> PartialEq for Crc<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.algorithm == other.algorithm
+    }
+}
+
+impl<W: Width> Eq for Crc<W> {}
+
+impl<W: Width> Crc<W> {
+    /// new builds the engine of the given algorithm.
+    ///
+    /// # Panics
+    /// It panics if the width of the algorithm isn't in 1..=W::BITS.
+    pub fn new(algorithm: Algorithm<W>) -> Self {
+        assert!(
+            algorithm.width >= 1 && algorithm.width <= W::BITS,
+            "invalid width {} for {}-bit CRC",
+            algorithm.width,
+            W::BITS
+        );
+
+        let table = if algorithm.refin {
+            make_table_reflected(reflect(algorithm.poly.into_u64(), algorithm.width))
+        } else {
+            make_table_normal(algorithm.poly.into_u64() << (64 - algorithm.width))
+        };
+
+        Self { algorithm, table }
+    }
+
+    /// algorithm returns the parameters of the engine.
+    pub fn algorithm(&self) -> &Algorithm<W> {
+        &self.algorithm
+    }
+
+    /// checksum returns the CRC of data.
+    pub fn checksum(&self, data: &[u8]) -> W {
+        self.finalize(self.feed(self.init(), data))
+    }
+
+    /// update returns the result of adding the bytes in p to the crc, which is a value returned by
+    /// [checksum](Self::checksum) or update itself.
+    pub fn update(&self, crc: W, p: &[u8]) -> W {
+        self.finalize(self.feed(self.unfinalize(crc), p))
+    }
+
+    /// digest returns a new [Hash](crate::Hash) computing the CRC incrementally. Its [sum](crate::Hash::sum) method
+    /// will lay the value out in big-endian byte order.
+    pub fn digest(&self) -> Digest<'_, W> {
+        Digest::new(self)
+    }
+
+    /// into_digest is like [digest](Self::digest), but the returned [Hash](crate::Hash) owns the engine, so it can
+    /// outlive it.
+    pub fn into_digest(self) -> Digest<'static, W> {
+        Digest::owned(self)
+    }
+
+    /// init returns the register loaded with the initial value.
+    fn init(&self) -> u64 {
+        let Algorithm {
+            width, init, refin, ..
+        } = self.algorithm;
+
+        if refin {
+            reflect(init.into_u64(), width)
+        } else {
+            init.into_u64() << (64 - width)
+        }
+    }
+
+    /// feed returns the register after processing the bytes in p.
+    fn feed(&self, reg: u64, p: &[u8]) -> u64 {
+        let (t, mut reg) = (&self.table, reg);
+
+        if self.algorithm.refin {
+            for &v in p {
+                reg = t[((reg as u8) ^ v) as usize] ^ (reg >> 8);
+            }
+        } else {
+            for &v in p {
+                reg = t[(((reg >> 56) as u8) ^ v) as usize] ^ (reg << 8);
+            }
+        }
+
+        reg
+    }
+
+    /// finalize turns the register into the CRC value.
+    fn finalize(&self, reg: u64) -> W {
+        let Algorithm {
+            width,
+            refin,
+            refout,
+            xorout,
+            ..
+        } = self.algorithm;
+
+        let mut crc = if refin { reg } else { reg >> (64 - width) };
+        if refin != refout {
+            crc = reflect(crc, width);
+        }
+
+        W::from_u64(crc ^ xorout.into_u64())
+    }
+
+    /// unfinalize turns the CRC value back into the register, as the inverse of finalize.
+    fn unfinalize(&self, crc: W) -> u64 {
+        let Algorithm {
+            width,
+            refin,
+            refout,
+            xorout,
+            ..
+        } = self.algorithm;
+
+        let mut reg = crc.into_u64() ^ xorout.into_u64();
+        if refin != refout {
+            reg = reflect(reg, width);
+        }
+
+        if refin {
+            reg
+        } else {
+            reg << (64 - width)
+        }
+    }
+}
+
+/// make_table_reflected builds the table of a LSB-first algorithm from the reflected polynomial.
+fn make_table_reflected(poly: u64) -> [u64; 256] {
+    let mut out = [0u64; 256];
+    for (i, v) in out.iter_mut().enumerate() {
+        let mut crc = i as u64;
+        for _j in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ poly
+            } else {
+                crc >> 1
+            };
+        }
+        *v = crc;
+    }
+    out
+}
+
+/// make_table_normal builds the table of a MSB-first algorithm from the polynomial left-aligned in 64 bits.
+fn make_table_normal(poly: u64) -> [u64; 256] {
+    let mut out = [0u64; 256];
+    for (i, v) in out.iter_mut().enumerate() {
+        let mut crc = (i as u64) << 56;
+        for _j in 0..8 {
+            crc = if crc & (1 << 63) != 0 {
+                (crc << 1) ^ poly
+            } else {
+                crc << 1
+            };
+        }
+        *v = crc;
+    }
+    out
+}
+
+/// reflect reverses the low width bits of v.
+fn reflect(v: u64, width: u8) -> u64 {
+    v.reverse_bits() >> (64 - width)
+}
+
+mod digest;
+
+pub use digest::Digest;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,176 @@
+use super::*;
+use crate::{crc32, crc64, Hash, Hash16, Hash8};
+
+const CHECK_INPUT: &[u8] = b"123456789";
+
+#[test]
+fn check() {
+    macro_rules! check {
+        ($($alg:ident),*) => {
+            $(
+                let c = Crc::new($alg);
+                assert_eq!($alg.check, c.checksum(CHECK_INPUT), "{}", stringify!($alg));
+            )*
+        };
+    }
+
+    check!(
+        CRC_8_AUTOSAR,
+        CRC_8_CDMA2000,
+        CRC_8_DVB_S2,
+        CRC_8_MAXIM_DOW,
+        CRC_8_SMBUS
+    );
+    check!(
+        CRC_16_ARC,
+        CRC_16_IBM_3740,
+        CRC_16_IBM_SDLC,
+        CRC_16_KERMIT,
+        CRC_16_MODBUS,
+        CRC_16_XMODEM
+    );
+    check!(
+        CRC_32_BZIP2,
+        CRC_32_CKSUM,
+        CRC_32_ISCSI,
+        CRC_32_ISO_HDLC,
+        CRC_32_MPEG_2
+    );
+    check!(CRC_64_ECMA_182, CRC_64_GO_ISO, CRC_64_WE, CRC_64_XZ);
+}
+
+#[test]
+fn check_odd_widths() {
+    struct Test {
+        name: &'static str,
+        alg: Algorithm<u16>,
+    }
+
+    let test_vector = vec![
+        Test {
+            name: "CRC-3/ROHC",
+            alg: Algorithm {
+                width: 3,
+                poly: 0x3,
+                init: 0x7,
+                refin: true,
+                refout: true,
+                xorout: 0x0,
+                check: 0x6,
+            },
+        },
+        Test {
+            name: "CRC-5/USB",
+            alg: Algorithm {
+                width: 5,
+                poly: 0x05,
+                init: 0x1f,
+                refin: true,
+                refout: true,
+                xorout: 0x1f,
+                check: 0x19,
+            },
+        },
+        Test {
+            name: "CRC-7/MMC",
+            alg: Algorithm {
+                width: 7,
+                poly: 0x09,
+                init: 0x00,
+                refin: false,
+                refout: false,
+                xorout: 0x00,
+                check: 0x75,
+            },
+        },
+        Test {
+            name: "CRC-12/UMTS",
+            alg: Algorithm {
+                width: 12,
+                poly: 0x80f,
+                init: 0x000,
+                refin: false,
+                refout: true,
+                xorout: 0x000,
+                check: 0xdaf,
+            },
+        },
+    ];
+
+    for v in test_vector {
+        let c = Crc::new(v.alg);
+        assert_eq!(v.alg.check, c.checksum(CHECK_INPUT), "{}", v.name);
+    }
+}
+
+#[test]
+fn digest() {
+    let c = Crc::new(CRC_16_XMODEM);
+
+    let mut h = c.digest();
+    assert_eq!(2, h.size());
+
+    h.update(b"1234");
+    h.update(b"56789");
+    assert_eq!(vec![0x31, 0xc3], h.sum(None));
+    assert_eq!(vec![0xff, 0x31, 0xc3], h.sum(Some(vec![0xff])));
+
+    h.reset();
+    h.update(CHECK_INPUT);
+    assert_eq!(0x31c3, h.value());
+}
+
+#[test]
+fn into_digest() {
+    let mut h = Crc::new(CRC_8_SMBUS).into_digest();
+    assert_eq!(1, h.size());
+    assert_eq!(1, h.block_size());
+
+    h.update(CHECK_INPUT);
+    assert_eq!(CRC_8_SMBUS.check, h.sum8());
+    assert_eq!(vec![0xff, 0xf4], h.sum(Some(vec![0xff])));
+
+    let mut h = Crc::new(CRC_16_IBM_3740).into_digest();
+    assert_eq!(2, h.size());
+
+    h.update(CHECK_INPUT);
+    assert_eq!(CRC_16_IBM_3740.check, h.sum16());
+
+    h.reset();
+    assert_eq!(0xffff, h.sum16());
+}
+
+#[test]
+fn presets() {
+    let data = b"The quick brown fox jumps over the lazy dog";
+
+    let ieee = Crc::new(CRC_32_ISO_HDLC);
+    assert_eq!(crc32::checksum_ieee(data), ieee.checksum(data));
+
+    let castagnoli = Crc::new(CRC_32_ISCSI);
+    let t = crc32::make_table(crc32::CASTAGNOLI);
+    assert_eq!(crc32::checksum(data, &t), castagnoli.checksum(data));
+
+    let ecma = Crc::new(CRC_64_XZ);
+    let t = crc64::make_table(crc64::ECMA);
+    assert_eq!(crc64::checksum(data, &t), ecma.checksum(data));
+
+    let iso = Crc::new(CRC_64_GO_ISO);
+    let t = crc64::make_table(crc64::ISO);
+    assert_eq!(crc64::checksum(data, &t), iso.checksum(data));
+}
+
+#[test]
+#[should_panic]
+fn invalid_width() {
+    let mut alg = CRC_16_ARC;
+    alg.width = 17;
+    Crc::new(alg);
+}
+
+#[test]
+fn table_eq() {
+    assert!(Crc::new(CRC_16_KERMIT) == Crc::new(CRC_16_KERMIT));
+    assert!(Crc::new(CRC_16_KERMIT) != Crc::new(CRC_16_XMODEM));
+    assert!(Crc::new(CRC_8_SMBUS) != Crc::new(CRC_8_MAXIM_DOW));
+}
@@ -0,0 +1,63 @@
+//! Module crc16 implements the 16-bit cyclic redundancy check, or CRC-16, checksum.
+//!
+//! See <https://en.wikipedia.org/wiki/Cyclic_redundancy_check> for information.
+//!
+//! Unlike CRC-32, the CRC-16 variants in use differ in more than the polynomial, so each preset is an
+//! [Algorithm] of the [crc] engine, whose polynomial is in normal (MSB-first) representation.
+//!
+
+use crate::crc::{self, Algorithm, Crc};
+
+/// ARC, also known as CRC-16/IBM, used by the ARC archiver and LHA.
+pub const ARC: Algorithm<u16> = crc::CRC_16_ARC;
+
+/// CCITT_FALSE, catalogued as CRC-16/IBM-3740.
+pub const CCITT_FALSE: Algorithm<u16> = crc::CRC_16_IBM_3740;
+
+/// KERMIT, also known as CRC-16/CCITT, used by the Kermit protocol and Bluetooth.
+pub const KERMIT: Algorithm<u16> = crc::CRC_16_KERMIT;
+
+/// MODBUS is used by the Modbus RTU protocol.
+pub const MODBUS: Algorithm<u16> = crc::CRC_16_MODBUS;
+
+/// X25, catalogued as CRC-16/IBM-SDLC, used by HDLC and X.25.
+pub const X25: Algorithm<u16> = crc::CRC_16_IBM_SDLC;
+
+/// XMODEM is used by the XMODEM protocol.
+pub const XMODEM: Algorithm<u16> = crc::CRC_16_XMODEM;
+
+/// The size of a CRC-16 checksum in bytes.
+pub const SIZE: usize = 2;
+
+/// Table is the [Crc] engine of a CRC-16 algorithm, holding its precomputed 256-entry table.
+pub type Table = Crc<u16>;
+
+/// Digest computes the CRC-16 checksum incrementally, as returned by [new].
+pub type Digest = crc::Digest<'static, u16>;
+
+/// checksum returns the CRC-16 checksum of data using the algorithm represented by the Table.
+pub fn checksum(data: &[u8], table: &Table) -> u16 {
+    table.checksum(data)
+}
+
+/// make_table returns a Table constructed from the specified algorithm, e.g. one of the presets of this module.
+///
+/// # Panics
+/// It panics if the width of the algorithm isn't in 1..=16.
+pub fn make_table(algorithm: Algorithm<u16>) -> Table {
+    Crc::new(algorithm)
+}
+
+/// new creates a new [`Hash16`](crate::Hash16) computing the CRC-16 checksum using the algorithm represented by the
+/// Table. Its [`sum`](crate::Hash::sum) method will lay the value out in big-endian byte order.
+pub fn new(t: Table) -> Digest {
+    t.into_digest()
+}
+
+/// update returns the result of adding the bytes in p to the crc.
+pub fn update(crc: u16, t: &Table, p: &[u8]) -> u16 {
+    t.update(crc, p)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,98 @@
+use super::*;
+use crate::{Hash, Hash16};
+
+struct Test {
+    arc: u16,
+    ccitt_false: u16,
+    kermit: u16,
+    modbus: u16,
+    x25: u16,
+    xmodem: u16,
+    input: &'static [u8],
+}
+
+#[test]
+fn golden() {
+    let presets = [ARC, CCITT_FALSE, KERMIT, MODBUS, X25, XMODEM];
+
+    for g in GOLDEN_TEST_VECTOR.iter() {
+        let expect = [g.arc, g.ccitt_false, g.kermit, g.modbus, g.x25, g.xmodem];
+
+        for (alg, expect) in presets.iter().zip(expect) {
+            let t = make_table(*alg);
+            assert_eq!(expect, checksum(g.input, &t), "{:?}({:?})", alg, g.input);
+
+            for delta in 0..=g.input.len() {
+                let (a, b) = g.input.split_at(delta);
+
+                let got = update(checksum(a, &t), &t, b);
+                assert_eq!(
+                    expect, got,
+                    "{:?}: update at {} of {:?}",
+                    alg, delta, g.input
+                );
+
+                let mut h = new(t);
+                h.update(a);
+                h.update(b);
+                assert_eq!(
+                    expect,
+                    h.sum16(),
+                    "{:?}: write at {} of {:?}",
+                    alg,
+                    delta,
+                    g.input
+                );
+                assert_eq!(expect.to_be_bytes().to_vec(), h.sum(None));
+            }
+        }
+    }
+}
+
+const GOLDEN_TEST_VECTOR: &[Test] = &[
+    Test {
+        arc: 0x0000,
+        ccitt_false: 0xffff,
+        kermit: 0x0000,
+        modbus: 0xffff,
+        x25: 0x0000,
+        xmodem: 0x0000,
+        input: b"",
+    },
+    Test {
+        arc: 0xe8c1,
+        ccitt_false: 0x9d77,
+        kermit: 0x728f,
+        modbus: 0xa87e,
+        x25: 0x82f7,
+        xmodem: 0x7c87,
+        input: b"a",
+    },
+    Test {
+        arc: 0x9738,
+        ccitt_false: 0x514a,
+        kermit: 0x58e9,
+        modbus: 0x5749,
+        x25: 0x9e25,
+        xmodem: 0x9dd6,
+        input: b"abc",
+    },
+    Test {
+        arc: 0xbb3d,
+        ccitt_false: 0x29b1,
+        kermit: 0x2189,
+        modbus: 0x4b37,
+        x25: 0x906e,
+        xmodem: 0x31c3,
+        input: b"123456789",
+    },
+    Test {
+        arc: 0xfcdf,
+        ccitt_false: 0x8fdd,
+        kermit: 0xc459,
+        modbus: 0xa89c,
+        x25: 0x9358,
+        xmodem: 0xf0c8,
+        input: b"The quick brown fox jumps over the lazy dog",
+    },
+];
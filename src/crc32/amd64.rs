@@ -0,0 +1,247 @@
+//! AMD64-specific hardware-assisted CRC-32 algorithms.
+//!
+//! ref: <https://github.com/golang/go/blob/go1.19.4/src/hash/crc32/crc32_amd64.go>
+
+use core::arch::x86_64::{
+    __m128i, _mm_clmulepi64_si128, _mm_crc32_u64, _mm_crc32_u8, _mm_loadu_si128, _mm_set_epi64x,
+    _mm_storeu_si128, _mm_xor_si128,
+};
+
+use crate::crc32::{combine, simple, slicing8, CASTAGNOLI, IEEE_TABLE};
+
+// This is the same as the byte length used by Go's implementation, which in turn is inspired from Intel's white
+// paper "Fast CRC Computation for iSCSI Polynomial Using CRC32 Instruction".
+const CASTAGNOLI_K1: usize = 168;
+const CASTAGNOLI_K2: usize = 1344;
+
+// Folding constants for the IEEE polynomial over the distances of 512 (R2R1) and 128 (R4R3) bits, as (high, low).
+//
+// ref: https://github.com/golang/go/blob/go1.19.4/src/hash/crc32/crc32_amd64.s
+const R2R1: (i64, i64) = (0x1c6e41596, 0x154442bd4);
+const R4R3: (i64, i64) = (0x0ccaa009e, 0x1751997d0);
+
+/// Sse42Table shifts a CRC over a sequence of K zero bytes, one byte of the CRC at a time.
+type Sse42Table = [[u32; 256]; 4];
+
+static CASTAGNOLI_SSE42_TABLE_K1: Sse42Table = make_shift_table(CASTAGNOLI_K1);
+
+static CASTAGNOLI_SSE42_TABLE_K2: Sse42Table = make_shift_table(CASTAGNOLI_K2);
+
+/// available_castagnoli tells if the CPU supports the SSE4.2 `crc32` instruction.
+/// Without `std`, it relies on the target features enabled at compile time.
+pub fn available_castagnoli() -> bool {
+    #[cfg(feature = "std")]
+    {
+        is_x86_feature_detected!("sse4.2")
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(target_feature = "sse4.2")
+    }
+}
+
+/// update_castagnoli returns the result of adding the bytes in p to the crc with the SSE4.2 `crc32` instruction.
+///
+/// # Safety
+/// The caller must ensure [available_castagnoli] returns true.
+#[target_feature(enable = "sse4.2")]
+pub unsafe fn update_castagnoli(crc: u32, p: &[u8]) -> u32 {
+    let (mut crc, mut p) = (!crc, p);
+
+    // If a buffer is long enough to use the optimization, process the first few
+    // bytes to align the buffer to an 8 byte boundary (if necessary).
+    if p.len() >= CASTAGNOLI_K1 * 3 {
+        let delta = (p.as_ptr() as usize) & 7;
+        if delta != 0 {
+            let delta = 8 - delta;
+            crc = castagnoli_sse42(crc, &p[..delta]);
+            p = &p[delta..];
+        }
+    }
+
+    // Process 3*K2 at a time.
+    while p.len() >= CASTAGNOLI_K2 * 3 {
+        // Compute CRC(I, A), CRC(0, B), and CRC(0, C).
+        let (crc_a, crc_b, crc_c) = castagnoli_sse42_triple(crc, 0, 0, p, CASTAGNOLI_K2);
+        // CRC(I, AB) = CRC(CRC(I, A), O) xor CRC(0, B)
+        let crc_ab = castagnoli_shift(&CASTAGNOLI_SSE42_TABLE_K2, crc_a) ^ crc_b;
+        // CRC(I, ABC) = CRC(CRC(I, AB), O) xor CRC(0, C)
+        crc = castagnoli_shift(&CASTAGNOLI_SSE42_TABLE_K2, crc_ab) ^ crc_c;
+        p = &p[CASTAGNOLI_K2 * 3..];
+    }
+
+    // Process 3*K1 at a time.
+    while p.len() >= CASTAGNOLI_K1 * 3 {
+        let (crc_a, crc_b, crc_c) = castagnoli_sse42_triple(crc, 0, 0, p, CASTAGNOLI_K1);
+        let crc_ab = castagnoli_shift(&CASTAGNOLI_SSE42_TABLE_K1, crc_a) ^ crc_b;
+        crc = castagnoli_shift(&CASTAGNOLI_SSE42_TABLE_K1, crc_ab) ^ crc_c;
+        p = &p[CASTAGNOLI_K1 * 3..];
+    }
+
+    // Use the simple implementation for what's left.
+    !castagnoli_sse42(crc, p)
+}
+
+/// available_ieee tells if the CPU supports the `pclmulqdq` instruction.
+/// Without `std`, it relies on the target features enabled at compile time.
+pub fn available_ieee() -> bool {
+    #[cfg(feature = "std")]
+    {
+        is_x86_feature_detected!("pclmulqdq")
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(target_feature = "pclmulqdq")
+    }
+}
+
+/// update_ieee returns the result of adding the bytes in p to the crc by folding the data with the `pclmulqdq`
+/// instruction.
+///
+/// # Safety
+/// The caller must ensure [available_ieee] returns true.
+#[target_feature(enable = "pclmulqdq")]
+pub unsafe fn update_ieee(crc: u32, p: &[u8]) -> u32 {
+    let (mut crc, mut p) = (crc, p);
+
+    if p.len() >= 64 {
+        let left = p.len() & 15;
+        let n = p.len() - left;
+        crc = !ieee_clmul(!crc, &p[..n]);
+        p = &p[n..];
+    }
+    if p.is_empty() {
+        return crc;
+    }
+
+    slicing8::update(crc, &IEEE_TABLE.slicing8, p)
+}
+
+/// castagnoli_sse42 updates the non-inverted crc with the given data.
+#[target_feature(enable = "sse4.2")]
+unsafe fn castagnoli_sse42(crc: u32, p: &[u8]) -> u32 {
+    let mut words = p.chunks_exact(8);
+
+    let mut crc = crc as u64;
+    for w in &mut words {
+        crc = _mm_crc32_u64(crc, read_u64(w));
+    }
+
+    let mut crc = crc as u32;
+    for &v in words.remainder() {
+        crc = _mm_crc32_u8(crc, v);
+    }
+
+    crc
+}
+
+/// castagnoli_sse42_triple updates three non-inverted crcs with the three consecutive blocks of k bytes starting
+/// at p, where k must be a multiple of 24.
+#[target_feature(enable = "sse4.2")]
+unsafe fn castagnoli_sse42_triple(
+    crc_a: u32,
+    crc_b: u32,
+    crc_c: u32,
+    p: &[u8],
+    k: usize,
+) -> (u32, u32, u32) {
+    let (a, b, c) = (&p[..k], &p[k..2 * k], &p[2 * k..3 * k]);
+
+    let (mut crc_a, mut crc_b, mut crc_c) = (crc_a as u64, crc_b as u64, crc_c as u64);
+    for i in (0..k).step_by(8) {
+        crc_a = _mm_crc32_u64(crc_a, read_u64(&a[i..]));
+        crc_b = _mm_crc32_u64(crc_b, read_u64(&b[i..]));
+        crc_c = _mm_crc32_u64(crc_c, read_u64(&c[i..]));
+    }
+
+    (crc_a as u32, crc_b as u32, crc_c as u32)
+}
+
+/// ieee_clmul updates the non-inverted crc with the given data, whose length must be a multiple of 16 and at least
+/// 64.
+///
+/// The data is folded into a single 128-bit value of the same remainder, which is then reduced by the table.
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn ieee_clmul(crc: u32, p: &[u8]) -> u32 {
+    let load = |b: &[u8]| _mm_loadu_si128(b.as_ptr() as *const __m128i);
+
+    let mut x1 = _mm_xor_si128(load(p), _mm_set_epi64x(0, crc as i64));
+    let mut x2 = load(&p[16..]);
+    let mut x3 = load(&p[32..]);
+    let mut x4 = load(&p[48..]);
+    let mut p = &p[64..];
+
+    let k = _mm_set_epi64x(R2R1.0, R2R1.1);
+    while p.len() >= 64 {
+        x1 = fold(x1, k, load(p));
+        x2 = fold(x2, k, load(&p[16..]));
+        x3 = fold(x3, k, load(&p[32..]));
+        x4 = fold(x4, k, load(&p[48..]));
+        p = &p[64..];
+    }
+
+    // Fold result into a single register (x1).
+    let k = _mm_set_epi64x(R4R3.0, R4R3.1);
+    x1 = fold(x1, k, x2);
+    x1 = fold(x1, k, x3);
+    x1 = fold(x1, k, x4);
+
+    while p.len() >= 16 {
+        x1 = fold(x1, k, load(p));
+        p = &p[16..];
+    }
+
+    let mut b = [0u8; 16];
+    _mm_storeu_si128(b.as_mut_ptr() as *mut __m128i, x1);
+
+    !simple::update(!0, &IEEE_TABLE.slicing8[0], &b)
+}
+
+/// fold moves x forward over the folding distance encoded by k, and adds it to the next block v.
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn fold(x: __m128i, k: __m128i, v: __m128i) -> __m128i {
+    let lo = _mm_clmulepi64_si128(x, k, 0x00);
+    let hi = _mm_clmulepi64_si128(x, k, 0x11);
+    _mm_xor_si128(_mm_xor_si128(lo, hi), v)
+}
+
+/// castagnoli_shift computes the CRC32-C of K1 or K2 zeroes (depending on the given table) seeded with the given crc.
+fn castagnoli_shift(t: &Sse42Table, crc: u32) -> u32 {
+    t[3][(crc >> 24) as usize]
+        ^ t[2][((crc >> 16) & 0xff) as usize]
+        ^ t[1][((crc >> 8) & 0xff) as usize]
+        ^ t[0][(crc & 0xff) as usize]
+}
+
+/// make_shift_table builds the table shifting a CRC over k zero bytes, where
+///
+/// ```text
+/// t[0][i] = CRC(i000, O)
+/// t[1][i] = CRC(0i00, O)
+/// t[2][i] = CRC(00i0, O)
+/// t[3][i] = CRC(000i, O)
+/// ```
+///
+/// and O is a sequence of k zeros. Shifting over O is a multiplication by x^(8k) modulo the polynomial.
+const fn make_shift_table(k: usize) -> Sse42Table {
+    let shift = combine::x8nmodp(k as u64, CASTAGNOLI);
+
+    let mut out = [[0u32; 256]; 4];
+    let mut b = 0;
+    while b < 4 {
+        let mut i = 0;
+        while i < 256 {
+            out[b][i] = combine::multmodp(shift, (i as u32) << (b * 8), CASTAGNOLI);
+            i += 1;
+        }
+        b += 1;
+    }
+
+    out
+}
+
+fn read_u64(p: &[u8]) -> u64 {
+    u64::from_le_bytes(p[..8].try_into().expect("&[u8] as [u8; 8]"))
+}
@@ -0,0 +1,91 @@
+//! ARM64-specific hardware-assisted CRC-32 algorithms.
+
+use core::arch::aarch64::vmull_p64;
+
+use crate::crc32::{simple, slicing8, IEEE_TABLE};
+
+// Folding constants for the IEEE polynomial over the distances of 512 (R2R1) and 128 (R4R3) bits, as (high, low).
+// They are the same as the ones used by the amd64 implementation.
+const R2R1: (u64, u64) = (0x1c6e41596, 0x154442bd4);
+const R4R3: (u64, u64) = (0x0ccaa009e, 0x1751997d0);
+
+/// available_ieee tells if the CPU supports the `pmull` instruction.
+/// Without `std`, it relies on the target features enabled at compile time.
+pub fn available_ieee() -> bool {
+    #[cfg(feature = "std")]
+    {
+        std::arch::is_aarch64_feature_detected!("pmull")
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(target_feature = "aes")
+    }
+}
+
+/// update_ieee returns the result of adding the bytes in p to the crc by folding the data with the `pmull`
+/// instruction.
+///
+/// # Safety
+/// The caller must ensure [available_ieee] returns true.
+#[target_feature(enable = "neon,aes")]
+pub unsafe fn update_ieee(crc: u32, p: &[u8]) -> u32 {
+    let (mut crc, mut p) = (crc, p);
+
+    if p.len() >= 64 {
+        let left = p.len() & 15;
+        let n = p.len() - left;
+        crc = !ieee_pmull(!crc, &p[..n]);
+        p = &p[n..];
+    }
+    if p.is_empty() {
+        return crc;
+    }
+
+    slicing8::update(crc, &IEEE_TABLE.slicing8, p)
+}
+
+/// ieee_pmull updates the non-inverted crc with the given data, whose length must be a multiple of 16 and at least
+/// 64.
+///
+/// The data is folded into a single 128-bit value of the same remainder, which is then reduced by the table.
+#[target_feature(enable = "neon,aes")]
+unsafe fn ieee_pmull(crc: u32, p: &[u8]) -> u32 {
+    let mut x1 = load(p) ^ (crc as u128);
+    let mut x2 = load(&p[16..]);
+    let mut x3 = load(&p[32..]);
+    let mut x4 = load(&p[48..]);
+    let mut p = &p[64..];
+
+    while p.len() >= 64 {
+        x1 = fold(x1, R2R1, load(p));
+        x2 = fold(x2, R2R1, load(&p[16..]));
+        x3 = fold(x3, R2R1, load(&p[32..]));
+        x4 = fold(x4, R2R1, load(&p[48..]));
+        p = &p[64..];
+    }
+
+    // Fold result into a single register (x1).
+    x1 = fold(x1, R4R3, x2);
+    x1 = fold(x1, R4R3, x3);
+    x1 = fold(x1, R4R3, x4);
+
+    while p.len() >= 16 {
+        x1 = fold(x1, R4R3, load(p));
+        p = &p[16..];
+    }
+
+    !simple::update(!0, &IEEE_TABLE.slicing8[0], &x1.to_le_bytes())
+}
+
+/// fold moves x forward over the folding distance encoded by k, and adds it to the next block v.
+#[target_feature(enable = "neon,aes")]
+unsafe fn fold(x: u128, k: (u64, u64), v: u128) -> u128 {
+    let lo = vmull_p64(x as u64, k.1);
+    let hi = vmull_p64((x >> 64) as u64, k.0);
+    lo ^ hi ^ v
+}
+
+fn load(p: &[u8]) -> u128 {
+    u128::from_le_bytes(p[..16].try_into().expect("&[u8] as [u8; 16]"))
+}
@@ -1,48 +1,117 @@
-use std::io::Write;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
 
-use crate::crc32::{self, Table, CASTAGNOLI_TABLE, IEEE_TABLE, SIZE};
-use crate::{Hash, Hash32};
+use crate::crc32::{self, Table, CASTAGNOLI, CASTAGNOLI_TABLE, IEEE, IEEE_TABLE, SIZE};
+use crate::{BinaryMarshaler, BinaryUnmarshaler, Error, Hash, Hash32};
 
+const MAGIC: &[u8] = b"crc\x01";
+const MARSHALED_SIZE: usize = MAGIC.len() + 4 + 4;
+
+/// Digest computes the CRC-32 checksum incrementally, as returned by [new](crate::crc32::new) and
+/// [new_ieee](crate::crc32::new_ieee). Its default is the one of the IEEE polynomial.
+#[derive(Clone)]
 pub struct Digest {
     crc: u32,
-    table: Table,
+    algorithm: Algorithm,
+}
+
+/// Algorithm is the implementation picked once at construction, so that writes go straight to it.
+///
+/// Tables of other polynomials are boxed, so that the digest itself stays small.
+#[derive(Clone)]
+enum Algorithm {
+    Castagnoli,
+    Ieee,
+    Slicing8(Box<Table>),
+    Slicing8Normal(Box<Table>),
 }
 
 impl Digest {
-    pub fn new(crc: u32, table: Table) -> Self {
-        Self { crc, table }
+    pub(super) fn new(crc: u32, table: Table) -> Self {
+        let algorithm = match (table.reflected, table.poly) {
+            (true, CASTAGNOLI) => Algorithm::Castagnoli,
+            (true, IEEE) => Algorithm::Ieee,
+            (true, _) => Algorithm::Slicing8(Box::new(table)),
+            (false, _) => Algorithm::Slicing8Normal(Box::new(table)),
+        };
+
+        Self { crc, algorithm }
+    }
+
+    fn table(&self) -> &Table {
+        match &self.algorithm {
+            Algorithm::Castagnoli => &CASTAGNOLI_TABLE,
+            Algorithm::Ieee => &IEEE_TABLE,
+            Algorithm::Slicing8(t) | Algorithm::Slicing8Normal(t) => t,
+        }
     }
 }
 
-impl Write for Digest {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.crc = if self.table.eq(&CASTAGNOLI_TABLE) {
-            crc32::update_castagnoli(self.crc, buf)
-        } else if self.table.eq(&IEEE_TABLE) {
-            crc32::update_ieee(self.crc, buf)
-        } else {
-            crc32::simple::update(self.crc, &self.table, buf)
-        };
+impl fmt::Debug for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Digest")
+            .field("crc", &self.crc)
+            .field("table", self.table())
+            .finish()
+    }
+}
+
+impl Default for Digest {
+    fn default() -> Self {
+        Self {
+            crc: 0,
+            algorithm: Algorithm::Ieee,
+        }
+    }
+}
 
-        Ok(buf.len())
+impl BinaryMarshaler for Digest {
+    fn marshal_binary(&self) -> Result<Vec<u8>, Error> {
+        let mut b = Vec::with_capacity(MARSHALED_SIZE);
+        b.extend_from_slice(MAGIC);
+        b.extend_from_slice(&table_sum(self.table()).to_be_bytes());
+        b.extend_from_slice(&self.crc.to_be_bytes());
+        Ok(b)
     }
+}
+
+impl BinaryUnmarshaler for Digest {
+    fn unmarshal_binary(&mut self, data: &[u8]) -> Result<(), Error> {
+        if !data.starts_with(MAGIC) {
+            return Err(Error::InvalidStateIdentifier);
+        }
+        if data.len() != MARSHALED_SIZE {
+            return Err(Error::InvalidStateSize);
+        }
+
+        let (sum, crc) = data[MAGIC.len()..].split_at(4);
+        if table_sum(self.table()) != u32::from_be_bytes(sum.try_into().expect("sum as [u8; 4]")) {
+            return Err(Error::TableMismatch);
+        }
+        self.crc = u32::from_be_bytes(crc.try_into().expect("crc as [u8; 4]"));
 
-    fn flush(&mut self) -> std::io::Result<()> {
         Ok(())
     }
 }
 
+crate::impl_write!(Digest);
+
 impl Hash for Digest {
-    fn sum(&mut self, b: Option<Vec<u8>>) -> Vec<u8> {
-        let s = self.sum32().to_be_bytes();
-        match b {
-            Some(v) => {
-                let mut v = v;
-                v.extend_from_slice(&s);
-                v
+    fn update(&mut self, p: &[u8]) {
+        self.crc = match &self.algorithm {
+            Algorithm::Castagnoli => crc32::update_castagnoli(self.crc, p),
+            Algorithm::Ieee => crc32::update_ieee(self.crc, p),
+            Algorithm::Slicing8(t) => crc32::slicing8::update(self.crc, &t.slicing8, p),
+            Algorithm::Slicing8Normal(t) => {
+                crc32::slicing8::update_normal(self.crc, &t.slicing8, p)
             }
-            None => s.to_vec(),
-        }
+        };
+    }
+
+    fn sum_into(&self, out: &mut [u8]) {
+        let s = self.sum32().to_be_bytes();
+        out[..s.len()].copy_from_slice(&s);
     }
 
     fn reset(&mut self) {
@@ -59,7 +128,13 @@ impl Hash for Digest {
 }
 
 impl Hash32 for Digest {
-    fn sum32(&mut self) -> u32 {
+    fn sum32(&self) -> u32 {
         self.crc
     }
 }
+
+/// table_sum returns the IEEE checksum of table t, with entries laid out in big-endian byte order.
+fn table_sum(t: &Table) -> u32 {
+    t.iter()
+        .fold(0, |crc, v| crc32::update_ieee(crc, &v.to_be_bytes()))
+}
@@ -6,7 +6,12 @@
 //!
 //! See <https://en.wikipedia.org/wiki/Mathematics_of_cyclic_redundancy_checks#Reversed_representations_and_reciprocal_polynomials> for information.
 //!
-use std::ops::{Deref, DerefMut};
+//! Tables built by [make_table_normal] take polynomials in normal (MSB-first) representation instead, as used by
+//! CRC-32/BZIP2 and CRC-32/MPEG-2. Variants with other parameters, e.g. CRC-32/CKSUM, are provided by the generic
+//! engine of [crc](crate::crc).
+//!
+use core::fmt;
+use core::ops::Deref;
 
 /// Castagnoli's polynomial, used in iSCSI.
 /// Has better error detection characteristics than IEEE.
@@ -17,6 +22,10 @@ pub const CASTAGNOLI: u32 = 0x82f63b78;
 /// Used by ethernet (IEEE 802.3), v.42, fddi, gzip, zip, png, ...
 pub const IEEE: u32 = 0xedb88320;
 
+/// IEEE_NORMAL is the IEEE polynomial in normal (MSB-first) representation, for tables built by
+/// [make_table_normal].
+pub const IEEE_NORMAL: u32 = 0x04c11db7;
+
 /// Koopman's polynomial.
 /// Also has better error detection characteristics than IEEE.
 /// <https://dx.doi.org/10.1109/DSN.2002.1028931>
@@ -25,26 +34,53 @@ pub const KOOPMAN: u32 = 0xeb31d82e;
 /// The size of a CRC-32 checksum in bytes.
 pub const SIZE: usize = 4;
 
-lazy_static::lazy_static! {
-
-  /// IEEE_TABLE is the table for the IEEE polynomial.
-  pub static ref IEEE_TABLE: Table = simple::make_table(IEEE);
+/// IEEE_TABLE is the table for the IEEE polynomial.
+pub static IEEE_TABLE: Table = Table::from_poly(IEEE);
 
-  static ref  CASTAGNOLI_TABLE: Table = simple::make_table(CASTAGNOLI);
+static CASTAGNOLI_TABLE: Table = Table::from_poly(CASTAGNOLI);
 
-  static ref CASTAGNOLI_TABLE8: Slicing8Table = slicing8::make_table(CASTAGNOLI);
+/// Table is a 256-word table representing the polynomial for efficient processing.
+///
+/// It also carries the companion tables of the slicing-by-8 algorithm, so checksums of any polynomial are computed
+/// 8 bytes at a time. This makes it 8 KiB large, so it isn't [Copy]: [digests](Digest) box it instead of holding
+/// it inline.
+#[derive(Clone)]
+pub struct Table {
+    poly: u32,
+    reflected: bool,
+    slicing8: Slicing8Table,
+}
 
+impl Table {
+    /// from_poly builds the Table of the specified polynomial, like [make_table] does for the polynomials
+    /// without a prebuilt table.
+    ///
+    /// Being a const fn, it builds tables of custom polynomials at compile time, so they can be kept in statics.
+    pub const fn from_poly(poly: u32) -> Self {
+        Self {
+            poly,
+            reflected: true,
+            slicing8: slicing8::make_table(poly),
+        }
+    }
 
-  static ref IEEE_TABLE8: Slicing8Table = slicing8::make_table(IEEE);
+    /// from_poly_normal builds the Table of the specified polynomial in normal (MSB-first) representation, like
+    /// [make_table_normal]. It can be evaluated at compile time as well.
+    pub const fn from_poly_normal(poly: u32) -> Self {
+        Self {
+            poly,
+            reflected: false,
+            slicing8: slicing8::make_table_normal(poly),
+        }
+    }
 }
 
-/// Table is a 256-word table representing the polynomial for efficient processing.
-#[derive(PartialEq, Eq, Clone, Copy)]
-pub struct Table(pub [u32; 256]);
-
-impl Default for Table {
-    fn default() -> Self {
-        Self([0u32; 256])
+impl fmt::Debug for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Table")
+            .field("poly", &format_args!("{:#010x}", self.poly))
+            .field("reflected", &self.reflected)
+            .finish_non_exhaustive()
     }
 }
 
@@ -52,16 +88,19 @@ impl Deref for Table {
     type Target = [u32; 256];
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.slicing8[0]
     }
 }
 
-impl DerefMut for Table {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+// Tables of the same polynomial and bit order are always identical, so comparing them is enough.
+impl PartialEq for Table {
+    fn eq(&self, other: &Self) -> bool {
+        self.poly == other.poly && self.reflected == other.reflected
     }
 }
 
+impl Eq for Table {}
+
 /// checksum returns the CRC-32 checksum of data using the polynomial represented by the Table.
 pub fn checksum(data: &[u8], table: &Table) -> u32 {
     update(0, table, data)
@@ -72,9 +111,28 @@ pub fn checksum_ieee(data: &[u8]) -> u32 {
     update_ieee(0, data)
 }
 
+/// combine returns the CRC-32 checksum of the concatenation A||B, given crc1 of A, crc2 of B and the length of B
+/// in bytes. Both checksums must be computed with the polynomial poly, which is in the same reversed representation
+/// as [IEEE], [CASTAGNOLI] and [KOOPMAN]. Checksums computed with tables built by [make_table_normal] are not
+/// supported, and combining them gives meaningless results.
+///
+/// It follows the semantics of zlib's `crc32_combine`, so checksums of chunks processed in parallel can be merged
+/// without rereading data.
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/crc32_combine.rs")]
+/// ```
+pub fn combine(crc1: u32, crc2: u32, len2: u64, poly: u32) -> u32 {
+    combine::multmodp(combine::x8nmodp(len2, poly), crc1, poly) ^ crc2
+}
+
 /// make_table returns a Table constructed from the specified polynomial. The contents of this Table must not be
 /// modified.
 ///
+/// The tables of [IEEE] and [CASTAGNOLI] are copied from prebuilt ones rather than computed again. Tables to be
+/// kept in statics are built at compile time by [Table::from_poly].
+///
 /// # Example
 /// ```
 #[doc = include_str!("../../examples/crc32_make_table.rs")]
@@ -82,48 +140,94 @@ pub fn checksum_ieee(data: &[u8]) -> u32 {
 ///
 pub fn make_table(poly: u32) -> Table {
     match poly {
-        IEEE => *IEEE_TABLE,
-        CASTAGNOLI => *CASTAGNOLI_TABLE,
-        _ => simple::make_table(poly),
+        IEEE => IEEE_TABLE.clone(),
+        CASTAGNOLI => CASTAGNOLI_TABLE.clone(),
+        _ => Table::from_poly(poly),
     }
 }
 
+/// make_table_normal returns a Table constructed from the specified polynomial in normal (MSB-first)
+/// representation, e.g. [IEEE_NORMAL]. The contents of this Table must not be modified. Tables to be kept in
+/// statics are built at compile time by [Table::from_poly_normal].
+///
+/// Checksums computed with such a table process each byte from its most significant bit, with the same initial
+/// value and final xor of all ones as the other tables, i.e. CRC-32/BZIP2 for [IEEE_NORMAL]. CRC-32/MPEG-2, which
+/// skips the final xor, is the bitwise complement of a finished checksum. The crc passed to and returned by
+/// [update] is always a CRC-32/BZIP2 one, so a running CRC-32/MPEG-2 checksum is complemented before being passed
+/// in, and the result is complemented again.
+///
+/// Such checksums can't be merged by [combine], which only handles polynomials in reversed representation.
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/crc32_make_table_normal.rs")]
+/// ```
+pub fn make_table_normal(poly: u32) -> Table {
+    Table::from_poly_normal(poly)
+}
+
 /// new creates a new [`Hash32`](crate::Hash32) computing the CRC-32 checksum using the polynomial represented by the Table. Its
-/// [`sum`](crate::Hash::sum) method will lay the value out in big-endian byte order.
-pub fn new(t: Table) -> impl crate::Hash32 {
+/// [`sum`](crate::Hash::sum) method will lay the value out in big-endian byte order. The returned Hash32 also
+/// implements [`BinaryMarshaler`](crate::BinaryMarshaler) and [`BinaryUnmarshaler`](crate::BinaryUnmarshaler) to
+/// marshal and unmarshal the internal state of the hash.
+pub fn new(t: Table) -> Digest {
     Digest::new(0, t)
 }
 
 /// new_ieee creates a new [`Hash32`](crate::Hash32) computing the CRC-32 checksum using the IEEE polynomial. Its
-/// [`sum`](crate::Hash::sum) method will lay the value out in big-endian byte order.
-pub fn new_ieee() -> impl crate::Hash32 {
-    new(*IEEE_TABLE)
+/// [`sum`](crate::Hash::sum) method will lay the value out in big-endian byte order. The returned Hash32 also
+/// implements [`BinaryMarshaler`](crate::BinaryMarshaler) and [`BinaryUnmarshaler`](crate::BinaryUnmarshaler) to
+/// marshal and unmarshal the internal state of the hash.
+pub fn new_ieee() -> Digest {
+    Digest::default()
 }
 
 /// update returns the result of adding the bytes in p to the crc.
 pub fn update(crc: u32, t: &Table, p: &[u8]) -> u32 {
-    if t.eq(&CASTAGNOLI_TABLE) {
-        update_castagnoli(crc, p)
-    } else if t.eq(&IEEE_TABLE) {
-        update_ieee(crc, p)
-    } else {
-        simple::update(crc, t, p)
+    match (t.reflected, t.poly) {
+        (true, CASTAGNOLI) => update_castagnoli(crc, p),
+        (true, IEEE) => update_ieee(crc, p),
+        (true, _) => slicing8::update(crc, &t.slicing8, p),
+        (false, _) => slicing8::update_normal(crc, &t.slicing8, p),
     }
 }
 
 fn update_castagnoli(crc: u32, p: &[u8]) -> u32 {
-    slicing8::update(crc, &CASTAGNOLI_TABLE8, p)
+    #[cfg(target_arch = "x86_64")]
+    if amd64::available_castagnoli() {
+        // SAFETY: the SSE4.2 support has just been detected.
+        return unsafe { amd64::update_castagnoli(crc, p) };
+    }
+
+    slicing8::update(crc, &CASTAGNOLI_TABLE.slicing8, p)
 }
 
 fn update_ieee(crc: u32, p: &[u8]) -> u32 {
-    slicing8::update(crc, &IEEE_TABLE8, p)
+    #[cfg(target_arch = "x86_64")]
+    if amd64::available_ieee() {
+        // SAFETY: the PCLMULQDQ support has just been detected.
+        return unsafe { amd64::update_ieee(crc, p) };
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    if arm64::available_ieee() {
+        // SAFETY: the PMULL support has just been detected.
+        return unsafe { arm64::update_ieee(crc, p) };
+    }
+
+    slicing8::update(crc, &IEEE_TABLE.slicing8, p)
 }
 
+#[cfg(target_arch = "x86_64")]
+mod amd64;
+#[cfg(target_arch = "aarch64")]
+mod arm64;
+mod combine;
 mod digest;
 mod simple;
 mod slicing8;
 
-use digest::Digest;
+pub use digest::Digest;
 use slicing8::Slicing8Table;
 
 #[cfg(test)]
@@ -1,26 +1,50 @@
-use crate::crc32::Table;
+/// make_table builds the table of the LSB-first poly in reversed representation.
+pub const fn make_table(poly: u32) -> [u32; 256] {
+    let mut out = [0u32; 256];
 
-pub fn make_table(poly: u32) -> Table {
-    let mut out = Table::default();
-    populate_table(poly, &mut out);
-    out
-}
-
-pub fn populate_table(poly: u32, t: &mut Table) {
-    for i in 0usize..256 {
+    let mut i = 0;
+    while i < 256 {
         let mut crc = i as u32;
-        for _j in 0..8 {
+        let mut j = 0;
+        while j < 8 {
             crc = if (crc & 1) == 1 {
                 (crc >> 1) ^ poly
             } else {
                 crc >> 1
             };
+            j += 1;
         }
-        t[i] = crc
+        out[i] = crc;
+        i += 1;
     }
+
+    out
 }
 
-pub fn update(crc: u32, t: &Table, p: &[u8]) -> u32 {
+/// make_table_normal builds the table of the MSB-first poly in normal representation.
+pub const fn make_table_normal(poly: u32) -> [u32; 256] {
+    let mut out = [0u32; 256];
+
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u32) << 24;
+        let mut j = 0;
+        while j < 8 {
+            crc = if (crc & (1 << 31)) != 0 {
+                (crc << 1) ^ poly
+            } else {
+                crc << 1
+            };
+            j += 1;
+        }
+        out[i] = crc;
+        i += 1;
+    }
+
+    out
+}
+
+pub fn update(crc: u32, t: &[u32; 256], p: &[u8]) -> u32 {
     let mut crc = !crc;
     for v in p {
         crc = t[((crc as u8) ^ v) as usize] ^ (crc >> 8);
@@ -28,3 +52,13 @@ pub fn update(crc: u32, t: &Table, p: &[u8]) -> u32 {
 
     !crc
 }
+
+/// update_normal is the counterpart of update for tables built by make_table_normal.
+pub fn update_normal(crc: u32, t: &[u32; 256], p: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for v in p {
+        crc = t[(((crc >> 24) as u8) ^ v) as usize] ^ (crc << 8);
+    }
+
+    !crc
+}
@@ -1,51 +1,46 @@
-use std::ops::{Deref, DerefMut};
-
-use crate::crc32::{simple, Table};
+use crate::crc32::simple;
 
 pub const SLICING8_CUTOFF: usize = 16;
 
-#[derive(Default)]
-pub struct Slicing8Table(pub [Table; 8]);
+/// Slicing8Table holds the 8 tables of the slicing-by-8 algorithm, where the first one is the simple table.
+pub type Slicing8Table = [[u32; 256]; 8];
 
-impl Deref for Slicing8Table {
-    type Target = [Table; 8];
+pub const fn make_table(poly: u32) -> Slicing8Table {
+    let mut out = [[0u32; 256]; 8];
+    out[0] = simple::make_table(poly);
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = out[0][i];
+        let mut j = 1;
+        while j < 8 {
+            crc = out[0][(crc & 0xff) as usize] ^ (crc >> 8);
+            out[j][i] = crc;
+            j += 1;
+        }
+        i += 1;
     }
-}
 
-impl DerefMut for Slicing8Table {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
+    out
 }
 
-pub fn make_table(poly: u32) -> Slicing8Table {
-    let mut out = Slicing8Table::default();
-    simple::populate_table(poly, &mut out[0]);
+/// make_table_normal is the counterpart of make_table for the MSB-first poly in normal representation.
+pub const fn make_table_normal(poly: u32) -> Slicing8Table {
+    let mut out = [[0u32; 256]; 8];
+    out[0] = simple::make_table_normal(poly);
 
-    for i in 0usize..256 {
+    let mut i = 0;
+    while i < 256 {
         let mut crc = out[0][i];
-        for j in 1usize..8 {
-            crc = out[0][(crc & 0xff) as usize] ^ (crc >> 8);
-            out[j][i] = crc
+        let mut j = 1;
+        while j < 8 {
+            crc = out[0][(crc >> 24) as usize] ^ (crc << 8);
+            out[j][i] = crc;
+            j += 1;
         }
+        i += 1;
     }
 
-    /*
-    fn show(t: &Table, i: usize) {
-        print!("[{i}] ");
-        for v in t.iter() {
-            print!("{v} ");
-        }
-        println!();
-    }
-    for (i, v) in out.iter().enumerate() {
-        show(v, i);
-    }
-    */
-
     out
 }
 
@@ -70,9 +65,35 @@ pub fn update(crc: u32, t: &Slicing8Table, p: &[u8]) -> u32 {
         }
         crc = !crc;
     }
-    if p.len() == 0 {
+    if p.is_empty() {
         return crc;
     }
 
     simple::update(crc, &t[0], p)
 }
+
+/// update_normal is the counterpart of update for tables built by make_table_normal.
+pub fn update_normal(crc: u32, t: &Slicing8Table, p: &[u8]) -> u32 {
+    let (mut crc, mut p) = (crc, p);
+    if p.len() >= SLICING8_CUTOFF {
+        crc = !crc;
+        while p.len() > 8 {
+            crc ^= u32::from_be_bytes([p[0], p[1], p[2], p[3]]);
+            crc = t[0][p[7] as usize]
+                ^ t[1][p[6] as usize]
+                ^ t[2][p[5] as usize]
+                ^ t[3][p[4] as usize]
+                ^ t[4][(crc & 0xff) as usize]
+                ^ t[5][((crc >> 8) & 0xff) as usize]
+                ^ t[6][((crc >> 16) & 0xff) as usize]
+                ^ t[7][(crc >> 24) as usize];
+            p = &p[8..];
+        }
+        crc = !crc;
+    }
+    if p.is_empty() {
+        return crc;
+    }
+
+    simple::update_normal(crc, &t[0], p)
+}
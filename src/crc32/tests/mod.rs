@@ -1,9 +1,60 @@
-use std::io::Write;
-
-use crate::Hash32;
+use crate::{BinaryMarshaler, BinaryUnmarshaler, Error, Hash, Hash32};
 
 use super::*;
 
+#[test]
+fn combine() {
+    for poly in [IEEE, CASTAGNOLI, KOOPMAN, 0xD5828281] {
+        let t = make_table(poly);
+
+        for g in GOLDEN_TEST_VECTOR.iter() {
+            let expect = checksum(g.input, &t);
+
+            for i in 0..=g.input.len() {
+                let (a, b) = g.input.split_at(i);
+
+                let got = super::combine(checksum(a, &t), checksum(b, &t), b.len() as u64, poly);
+                assert_eq!(
+                    expect,
+                    got,
+                    "poly={:#08x}: combine({}, {}) = {:#08x}, want {:#08x}",
+                    poly,
+                    String::from_utf8_lossy(a),
+                    String::from_utf8_lossy(b),
+                    got,
+                    expect
+                );
+            }
+        }
+
+        let mut p = vec![0u8; 100000];
+        let _ = getrandom::getrandom(&mut p);
+        let (a, b) = p.split_at(12345);
+        let got = super::combine(checksum(a, &t), checksum(b, &t), b.len() as u64, poly);
+        assert_eq!(checksum(&p, &t), got, "poly={:#08x}: combine large", poly);
+    }
+}
+
+#[test]
+fn digest() {
+    let mut h = Digest::default();
+    h.update(b"hello");
+    assert_eq!(
+        checksum_ieee(b"hello"),
+        h.sum32(),
+        "default digest isn't IEEE"
+    );
+
+    let mut h2 = h.clone();
+    h2.update(b" world");
+    assert_eq!(checksum_ieee(b"hello"), h.sum32(), "clone shares the state");
+    assert_eq!(checksum_ieee(b"hello world"), h2.sum32());
+
+    let h = new(make_table(CASTAGNOLI));
+    let got = format!("{:?}", h);
+    assert!(got.contains("0x82f63b78"), "{}", got);
+}
+
 #[test]
 fn golden() {
     golden_ieee(checksum_ieee);
@@ -12,8 +63,8 @@ fn golden() {
         let f = |b: &[u8]| -> u32 {
             let mut ieee = new_ieee();
             let d = b.len().min(delta as usize);
-            let _ = ieee.write(&b[..d]);
-            let _ = ieee.write(&b[d..]);
+            ieee.update(&b[..d]);
+            ieee.update(&b[d..]);
             ieee.sum32()
         };
         golden_ieee(f);
@@ -22,24 +73,121 @@ fn golden() {
     let castagnoli_table = make_table(CASTAGNOLI);
 
     let f = |b: &[u8]| -> u32 {
-        let mut c = new(castagnoli_table);
-        let _ = c.write(b);
+        let mut c = new(castagnoli_table.clone());
+        c.update(b);
         c.sum32()
     };
     golden_castagnoli(f);
 
     for delta in 1..=7 {
         let f = |b: &[u8]| -> u32 {
-            let mut c = new(castagnoli_table);
+            let mut c = new(castagnoli_table.clone());
             let d = b.len().min(delta as usize);
-            let _ = c.write(&b[..d]);
-            let _ = c.write(&b[d..]);
+            c.update(&b[..d]);
+            c.update(&b[d..]);
             c.sum32()
         };
         golden_castagnoli(f);
     }
 }
 
+#[test]
+fn digest_any_poly() {
+    for poly in [IEEE, CASTAGNOLI, KOOPMAN, 0xD5828281] {
+        let t = make_table(poly);
+
+        for g in GOLDEN_TEST_VECTOR.iter() {
+            let mut h = new(t.clone());
+            let (a, b) = g.input.split_at(g.input.len() / 3);
+            h.update(a);
+            h.update(b);
+
+            assert_eq!(
+                checksum(g.input, &t),
+                h.sum32(),
+                "poly={:#08x}: digest({})",
+                poly,
+                String::from_utf8_lossy(g.input)
+            );
+        }
+    }
+}
+
+#[test]
+fn golden_marshal() {
+    let castagnoli_table = make_table(CASTAGNOLI);
+
+    for g in GOLDEN_TEST_VECTOR.iter() {
+        let (head, tail) = g.input.split_at(g.input.len() / 2);
+
+        {
+            let mut h = new_ieee();
+            let mut h2 = new_ieee();
+
+            h.update(head);
+
+            let state = h.marshal_binary().expect("marshal_binary");
+            assert_eq!(
+                g.half_state_ieee,
+                state.as_slice(),
+                "IEEE checksum({}) state mismatch",
+                String::from_utf8_lossy(g.input)
+            );
+
+            h2.unmarshal_binary(&state).expect("unmarshal_binary");
+
+            h.update(tail);
+            h2.update(tail);
+
+            assert_eq!(
+                h.sum32(),
+                h2.sum32(),
+                "IEEE checksum({}) differs after restoring the state",
+                String::from_utf8_lossy(g.input)
+            );
+        }
+
+        {
+            let mut h = new(castagnoli_table.clone());
+            let mut h2 = new(castagnoli_table.clone());
+
+            h.update(head);
+
+            let state = h.marshal_binary().expect("marshal_binary");
+            assert_eq!(
+                g.half_state_castagnoli,
+                state.as_slice(),
+                "Castagnoli checksum({}) state mismatch",
+                String::from_utf8_lossy(g.input)
+            );
+
+            h2.unmarshal_binary(&state).expect("unmarshal_binary");
+
+            h.update(tail);
+            h2.update(tail);
+
+            assert_eq!(
+                h.sum32(),
+                h2.sum32(),
+                "Castagnoli checksum({}) differs after restoring the state",
+                String::from_utf8_lossy(g.input)
+            );
+        }
+    }
+}
+
+#[test]
+fn marshal_table_mismatch() {
+    let mut h1 = new_ieee();
+    let mut h2 = new(make_table(KOOPMAN));
+
+    let state = h1.marshal_binary().expect("marshal_binary");
+    assert_eq!(Err(Error::TableMismatch), h2.unmarshal_binary(&state));
+
+    let state = h2.marshal_binary().expect("marshal_binary");
+    assert_eq!(Err(Error::TableMismatch), h1.unmarshal_binary(&state));
+}
+
 #[test]
 fn simple() {
     let ieee = simple::make_table(IEEE);
@@ -65,21 +213,140 @@ fn slicing() {
         let f2 = |crc: u32, b: &[u8]| -> u32 { slicing8::update(crc, &t2, b) };
 
         cross_check(f1, f2);
+
+        let t3 = make_table(poly);
+        let f3 = |crc: u32, b: &[u8]| -> u32 { update(crc, &t3, b) };
+
+        cross_check(f1, f3);
+    }
+}
+
+#[test]
+fn slicing_normal() {
+    let bzip2 = crate::crc::Crc::new(crate::crc::CRC_32_BZIP2);
+    let t = make_table_normal(IEEE_NORMAL);
+    for g in GOLDEN_TEST_VECTOR.iter() {
+        let expect = bzip2.checksum(g.input);
+        assert_eq!(expect, checksum(g.input, &t), "bzip2({:?})", g.input);
+
+        for delta in 1..=7 {
+            let mut h = new(t.clone());
+            let d = g.input.len().min(delta);
+            h.update(&g.input[..d]);
+            h.update(&g.input[d..]);
+            assert_eq!(expect, h.sum32(), "bzip2({:?}) by {}", g.input, delta);
+        }
+    }
+
+    let mpeg2 = crate::crc::Crc::new(crate::crc::CRC_32_MPEG_2);
+    for g in GOLDEN_TEST_VECTOR.iter() {
+        let expect = mpeg2.checksum(g.input);
+        assert_eq!(expect, !checksum(g.input, &t), "mpeg2({:?})", g.input);
+
+        let (head, tail) = g.input.split_at(g.input.len() / 2);
+        let running = !checksum(head, &t);
+        assert_eq!(
+            expect,
+            !update(!running, &t, tail),
+            "mpeg2({:?}) resumed",
+            g.input
+        );
+    }
+
+    for poly in [IEEE_NORMAL, 0x1edc6f41, 0x814141ab] {
+        let t1 = simple::make_table_normal(poly);
+        let f1 = |crc: u32, b: &[u8]| -> u32 { simple::update_normal(crc, &t1, b) };
+
+        let t2 = slicing8::make_table_normal(poly);
+        let f2 = |crc: u32, b: &[u8]| -> u32 { slicing8::update_normal(crc, &t2, b) };
+
+        cross_check(f1, f2);
+
+        let t3 = make_table_normal(poly);
+        let f3 = |crc: u32, b: &[u8]| -> u32 { update(crc, &t3, b) };
+
+        cross_check(f1, f3);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn sse42() {
+    if !amd64::available_castagnoli() {
+        return;
+    }
+
+    let f = |crc: u32, b: &[u8]| -> u32 { unsafe { amd64::update_castagnoli(crc, b) } };
+    golden_castagnoli(|b: &[u8]| f(0, b));
+
+    let castagnoli = slicing8::make_table(CASTAGNOLI);
+    cross_check(f, |crc: u32, b: &[u8]| {
+        slicing8::update(crc, &castagnoli, b)
+    });
+
+    // unaligned buffers
+    let mut p = vec![0u8; 5000];
+    let _ = getrandom::getrandom(&mut p);
+    for offset in 1..8 {
+        let b = &p[offset..];
+        assert_eq!(
+            slicing8::update(0, &castagnoli, b),
+            f(0, b),
+            "mismatch for buffer offset {offset}"
+        );
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn pclmulqdq() {
+    if !amd64::available_ieee() {
+        return;
     }
+
+    let f = |crc: u32, b: &[u8]| -> u32 { unsafe { amd64::update_ieee(crc, b) } };
+    golden_ieee(|b: &[u8]| f(0, b));
+
+    let ieee = slicing8::make_table(IEEE);
+    cross_check(f, |crc: u32, b: &[u8]| slicing8::update(crc, &ieee, b));
+}
+
+#[cfg(target_arch = "aarch64")]
+#[test]
+fn pmull() {
+    if !arm64::available_ieee() {
+        return;
+    }
+
+    let f = |crc: u32, b: &[u8]| -> u32 { unsafe { arm64::update_ieee(crc, b) } };
+    golden_ieee(|b: &[u8]| f(0, b));
+
+    let ieee = slicing8::make_table(IEEE);
+    cross_check(f, |crc: u32, b: &[u8]| slicing8::update(crc, &ieee, b));
 }
 
 #[test]
 fn table_eq() {
-    let a = simple::make_table(IEEE);
+    let a = make_table(IEEE);
     assert!(a.eq(&IEEE_TABLE));
+
+    assert_eq!(simple::make_table(IEEE), *a);
+    assert_eq!(simple::make_table(CASTAGNOLI), *make_table(CASTAGNOLI));
+    assert_eq!(*Table::from_poly(KOOPMAN), *make_table(KOOPMAN));
+
+    assert!(make_table_normal(IEEE) != make_table(IEEE));
+    assert_eq!(
+        simple::make_table_normal(IEEE_NORMAL),
+        *make_table_normal(IEEE_NORMAL)
+    );
 }
 
 struct Test {
     ieee: u32,
     castagnoli: u32,
     input: &'static [u8],
-    _half_state_ieee: &'static [u8],
-    _half_state_castagnoli: &'static [u8],
+    half_state_ieee: &'static [u8],
+    half_state_castagnoli: &'static [u8],
 }
 
 impl Test {
@@ -87,15 +354,15 @@ impl Test {
         ieee: u32,
         castagnoli: u32,
         input: &'static [u8],
-        _half_state_ieee: &'static [u8],
-        _half_state_castagnoli: &'static [u8],
+        half_state_ieee: &'static [u8],
+        half_state_castagnoli: &'static [u8],
     ) -> Self {
         Self {
             ieee,
             castagnoli,
             input,
-            _half_state_ieee,
-            _half_state_castagnoli,
+            half_state_ieee,
+            half_state_castagnoli,
         }
     }
 }
@@ -109,8 +376,8 @@ lazy_static::lazy_static! {
     // '\xca\x87' is encoded as bytes '\xca\x87'
     Test::new(0x0,0x0,b"",b"crc\x01\xca\x87\x91M\x00\x00\x00\x00",b"crc\x01wB\x84\x81\x00\x00\x00\x00"),
     Test::new(0xe8b7be43, 0xc1d04330, b"a", b"crc\x01\xca\x87\x91M\x00\x00\x00\x00", b"crc\x01wB\x84\x81\x00\x00\x00\x00"),
-    Test::new(0x9e83486d, 0xe2a22936, b"ab", b"crc\x01\xca\x87\x91M\xc2\x91C", b"crc\x01wB\x84\x81\xc1\xd0C0"),
-    Test::new(0x352441c2, 0x364b3fb7, b"abc", b"crc\x01\xca\x87\x91M\xc2\x91C", b"crc\x01wB\x84\x81\xc1\xd0C0"),
+    Test::new(0x9e83486d, 0xe2a22936, b"ab", b"crc\x01\xca\x87\x91M\xe8\xb7\xbeC", b"crc\x01wB\x84\x81\xc1\xd0C0"),
+    Test::new(0x352441c2, 0x364b3fb7, b"abc", b"crc\x01\xca\x87\x91M\xe8\xb7\xbeC", b"crc\x01wB\x84\x81\xc1\xd0C0"),
     Test::new(0xed82cd11, 0x92c80a31, b"abcd", b"crc\x01\xca\x87\x91M\x9e\x83Hm", b"crc\x01wB\x84\x81\xe2\xa2)6"),
     Test::new(0x8587d865, 0xc450d697, b"abcde", b"crc\x01\xca\x87\x91M\x9e\x83Hm", b"crc\x01wB\x84\x81\xe2\xa2)6"),
     Test::new(0x4b8e39ef, 0x53bceff1, b"abcdef", b"crc\x01\xca\x87\x91M5$A\xc2", b"crc\x01wB\x84\x816K?\xb7"),
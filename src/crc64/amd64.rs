@@ -0,0 +1,69 @@
+//! AMD64-specific hardware-assisted CRC-64 algorithms.
+
+use core::arch::x86_64::{
+    __m128i, _mm_clmulepi64_si128, _mm_loadu_si128, _mm_set_epi64x, _mm_storeu_si128, _mm_xor_si128,
+};
+
+use crate::crc64::clmul::Folding;
+
+/// available tells if the CPU supports the `pclmulqdq` instruction.
+/// Without `std`, it relies on the target features enabled at compile time.
+pub fn available() -> bool {
+    #[cfg(feature = "std")]
+    {
+        is_x86_feature_detected!("pclmulqdq")
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(target_feature = "pclmulqdq")
+    }
+}
+
+/// fold folds p into a 128-bit block of the same remainder as the non-inverted crc followed by p, where the length
+/// of p must be a multiple of 16 and at least 64.
+///
+/// # Safety
+/// The caller must ensure [available] returns true.
+#[target_feature(enable = "pclmulqdq")]
+pub unsafe fn fold(crc: u64, k: &Folding, p: &[u8]) -> [u8; 16] {
+    let load = |b: &[u8]| _mm_loadu_si128(b.as_ptr() as *const __m128i);
+
+    let mut x1 = _mm_xor_si128(load(p), _mm_set_epi64x(0, crc as i64));
+    let mut x2 = load(&p[16..]);
+    let mut x3 = load(&p[32..]);
+    let mut x4 = load(&p[48..]);
+    let mut p = &p[64..];
+
+    let k512 = _mm_set_epi64x(k.k512.1 as i64, k.k512.0 as i64);
+    while p.len() >= 64 {
+        x1 = fold128(x1, k512, load(p));
+        x2 = fold128(x2, k512, load(&p[16..]));
+        x3 = fold128(x3, k512, load(&p[32..]));
+        x4 = fold128(x4, k512, load(&p[48..]));
+        p = &p[64..];
+    }
+
+    // Fold result into a single register (x1).
+    let k128 = _mm_set_epi64x(k.k128.1 as i64, k.k128.0 as i64);
+    x1 = fold128(x1, k128, x2);
+    x1 = fold128(x1, k128, x3);
+    x1 = fold128(x1, k128, x4);
+
+    while p.len() >= 16 {
+        x1 = fold128(x1, k128, load(p));
+        p = &p[16..];
+    }
+
+    let mut out = [0u8; 16];
+    _mm_storeu_si128(out.as_mut_ptr() as *mut __m128i, x1);
+    out
+}
+
+/// fold128 moves x forward over the folding distance encoded by k, and adds it to the next block v.
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn fold128(x: __m128i, k: __m128i, v: __m128i) -> __m128i {
+    let lo = _mm_clmulepi64_si128(x, k, 0x00);
+    let hi = _mm_clmulepi64_si128(x, k, 0x11);
+    _mm_xor_si128(_mm_xor_si128(lo, hi), v)
+}
@@ -0,0 +1,65 @@
+//! ARM64-specific hardware-assisted CRC-64 algorithms.
+
+use core::arch::aarch64::vmull_p64;
+
+use crate::crc64::clmul::Folding;
+
+/// available tells if the CPU supports the `pmull` instruction.
+/// Without `std`, it relies on the target features enabled at compile time.
+pub fn available() -> bool {
+    #[cfg(feature = "std")]
+    {
+        std::arch::is_aarch64_feature_detected!("pmull")
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(target_feature = "aes")
+    }
+}
+
+/// fold folds p into a 128-bit block of the same remainder as the non-inverted crc followed by p, where the length
+/// of p must be a multiple of 16 and at least 64.
+///
+/// # Safety
+/// The caller must ensure [available] returns true.
+#[target_feature(enable = "neon,aes")]
+pub unsafe fn fold(crc: u64, k: &Folding, p: &[u8]) -> [u8; 16] {
+    let mut x1 = load(p) ^ (crc as u128);
+    let mut x2 = load(&p[16..]);
+    let mut x3 = load(&p[32..]);
+    let mut x4 = load(&p[48..]);
+    let mut p = &p[64..];
+
+    while p.len() >= 64 {
+        x1 = fold128(x1, k.k512, load(p));
+        x2 = fold128(x2, k.k512, load(&p[16..]));
+        x3 = fold128(x3, k.k512, load(&p[32..]));
+        x4 = fold128(x4, k.k512, load(&p[48..]));
+        p = &p[64..];
+    }
+
+    // Fold result into a single register (x1).
+    x1 = fold128(x1, k.k128, x2);
+    x1 = fold128(x1, k.k128, x3);
+    x1 = fold128(x1, k.k128, x4);
+
+    while p.len() >= 16 {
+        x1 = fold128(x1, k.k128, load(p));
+        p = &p[16..];
+    }
+
+    x1.to_le_bytes()
+}
+
+/// fold128 moves x forward over the folding distance encoded by k, and adds it to the next block v.
+#[target_feature(enable = "neon,aes")]
+unsafe fn fold128(x: u128, k: (u64, u64), v: u128) -> u128 {
+    let lo = vmull_p64(x as u64, k.0);
+    let hi = vmull_p64((x >> 64) as u64, k.1);
+    lo ^ hi ^ v
+}
+
+fn load(p: &[u8]) -> u128 {
+    u128::from_le_bytes(p[..16].try_into().expect("&[u8] as [u8; 16]"))
+}
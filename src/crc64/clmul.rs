@@ -0,0 +1,35 @@
+//! Constants shared by the hardware-assisted CRC-64 algorithms folding data with carry-less multiplication.
+//!
+//! A 128-bit block X, whose low and high 64-bit halves are L and H in reversed representation, stands for
+//! L * x^64 + H. Folding X over d bits means computing X * x^d mod P, i.e. L * x^(64+d) + H * x^d mod P. Since the
+//! carry-less product of two reversed 64-bit values is one bit short within a 128-bit block, it takes
+//! K1 = x^(64+d-1) mod P and K2 = x^(d-1) mod P as multipliers.
+
+use crate::crc64::combine;
+
+/// Folding holds the (K1, K2) multipliers of a polynomial for the folding distances of 512 and 128 bits.
+pub struct Folding {
+    pub k512: (u64, u64),
+    pub k128: (u64, u64),
+}
+
+/// Folding constants of the ECMA polynomial, i.e. CRC-64/XZ.
+pub const ECMA: Folding = Folding::new(crate::crc64::ECMA);
+
+/// Folding constants of the ISO polynomial.
+pub const ISO: Folding = Folding::new(crate::crc64::ISO);
+
+impl Folding {
+    const fn new(poly: u64) -> Self {
+        Self {
+            k512: (
+                combine::xnmodp(64 + 512 - 1, poly),
+                combine::xnmodp(512 - 1, poly),
+            ),
+            k128: (
+                combine::xnmodp(64 + 128 - 1, poly),
+                combine::xnmodp(128 - 1, poly),
+            ),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Arithmetic over GF(2) polynomials modulo a CRC-64 polynomial in reversed representation, where the most
+//! significant bit stands for x^0.
+//!
+//! ref: <https://github.com/madler/zlib/blob/v1.3/crc32.c>
+
+/// multmodp returns a(x) * b(x) mod p(x).
+pub const fn multmodp(a: u64, b: u64, poly: u64) -> u64 {
+    let (mut a, mut b) = (a, b);
+
+    let mut p = 0u64;
+    while a != 0 {
+        if a & (1 << 63) != 0 {
+            p ^= b;
+        }
+        a <<= 1;
+        b = if b & 1 == 1 { (b >> 1) ^ poly } else { b >> 1 };
+    }
+
+    p
+}
+
+/// xnmodp returns x^n mod p(x).
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub const fn xnmodp(n: u64, poly: u64) -> u64 {
+    let mut n = n;
+
+    let mut p = 1u64 << 63; // x^0 == 1
+    let mut x2k = 1u64 << 62; // x^1
+    while n != 0 {
+        if n & 1 == 1 {
+            p = multmodp(x2k, p, poly);
+        }
+        n >>= 1;
+        x2k = multmodp(x2k, x2k, poly);
+    }
+
+    p
+}
+
+/// x8nmodp returns x^(8n) mod p(x), i.e. the operator shifting a CRC over n zero bytes.
+pub fn x8nmodp(n: u64, poly: u64) -> u64 {
+    let mut n = n;
+
+    let mut p = 1u64 << 63; // x^0 == 1
+    let mut x2k = 1u64 << 55; // x^8
+    while n != 0 {
+        if n & 1 == 1 {
+            p = multmodp(x2k, p, poly);
+        }
+        n >>= 1;
+        x2k = multmodp(x2k, x2k, poly);
+    }
+
+    p
+}
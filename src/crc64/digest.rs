@@ -1,41 +1,107 @@
-use std::io::Write;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
 
-use crate::crc64::{self, Table, SIZE};
-use crate::{Hash, Hash64};
+use crate::crc64::{self, Table, ECMA, ECMA_TABLE, ISO, ISO_TABLE, SIZE};
+use crate::{BinaryMarshaler, BinaryUnmarshaler, Error, Hash, Hash64};
 
+const MAGIC: &[u8] = b"crc\x02";
+const MARSHALED_SIZE: usize = MAGIC.len() + 8 + 8;
+
+/// Digest computes the CRC-64 checksum incrementally, as returned by [new](crate::crc64::new). Its default is the
+/// one of the [ECMA](crate::crc64::ECMA) polynomial.
+#[derive(Clone)]
 pub struct Digest {
     crc: u64,
-    table: Table,
+    table: TableRef,
+}
+
+/// TableRef points to the table of a digest: the standard ones are statics, and the others are boxed so that the
+/// digest itself stays small.
+#[derive(Clone)]
+enum TableRef {
+    Ecma,
+    Iso,
+    Owned(Box<Table>),
 }
 
 impl Digest {
-    pub fn new(crc: u64, table: Table) -> Self {
+    pub(super) fn new(crc: u64, table: Table) -> Self {
+        let table = match table.poly {
+            ECMA => TableRef::Ecma,
+            ISO => TableRef::Iso,
+            _ => TableRef::Owned(Box::new(table)),
+        };
+
         Self { crc, table }
     }
+
+    fn table(&self) -> &Table {
+        match &self.table {
+            TableRef::Ecma => &ECMA_TABLE,
+            TableRef::Iso => &ISO_TABLE,
+            TableRef::Owned(t) => t,
+        }
+    }
 }
 
-impl Write for Digest {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.crc = crc64::update(self.crc, &self.table, buf);
+impl fmt::Debug for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Digest")
+            .field("crc", &self.crc)
+            .field("table", self.table())
+            .finish()
+    }
+}
 
-        Ok(buf.len())
+impl Default for Digest {
+    fn default() -> Self {
+        Self {
+            crc: 0,
+            table: TableRef::Ecma,
+        }
     }
+}
+
+impl BinaryMarshaler for Digest {
+    fn marshal_binary(&self) -> Result<Vec<u8>, Error> {
+        let mut b = Vec::with_capacity(MARSHALED_SIZE);
+        b.extend_from_slice(MAGIC);
+        b.extend_from_slice(&table_sum(self.table()).to_be_bytes());
+        b.extend_from_slice(&self.crc.to_be_bytes());
+        Ok(b)
+    }
+}
+
+impl BinaryUnmarshaler for Digest {
+    fn unmarshal_binary(&mut self, data: &[u8]) -> Result<(), Error> {
+        if !data.starts_with(MAGIC) {
+            return Err(Error::InvalidStateIdentifier);
+        }
+        if data.len() != MARSHALED_SIZE {
+            return Err(Error::InvalidStateSize);
+        }
+
+        let (sum, crc) = data[MAGIC.len()..].split_at(8);
+        if table_sum(self.table()) != u64::from_be_bytes(sum.try_into().expect("sum as [u8; 8]")) {
+            return Err(Error::TableMismatch);
+        }
+        self.crc = u64::from_be_bytes(crc.try_into().expect("crc as [u8; 8]"));
 
-    fn flush(&mut self) -> std::io::Result<()> {
         Ok(())
     }
 }
 
+crate::impl_write!(Digest);
+
 impl Hash for Digest {
-    fn sum(&mut self, b: Option<Vec<u8>>) -> Vec<u8> {
+    fn update(&mut self, p: &[u8]) {
+        self.crc = crc64::update(self.crc, self.table(), p);
+    }
+
+    fn sum_into(&self, out: &mut [u8]) {
         let s = self.sum64().to_be_bytes();
-        match b {
-            Some(mut v) => {
-                v.extend_from_slice(&s);
-                v
-            }
-            None => s.to_vec(),
-        }
+        out[..s.len()].copy_from_slice(&s);
     }
 
     fn reset(&mut self) {
@@ -52,7 +118,13 @@ impl Hash for Digest {
 }
 
 impl Hash64 for Digest {
-    fn sum64(&mut self) -> u64 {
+    fn sum64(&self) -> u64 {
         self.crc
     }
 }
+
+/// table_sum returns the ISO checksum of table t, with entries laid out in big-endian byte order.
+fn table_sum(t: &Table) -> u64 {
+    t.iter()
+        .fold(0, |crc, v| crc64::update(crc, &ISO_TABLE, &v.to_be_bytes()))
+}
@@ -2,10 +2,11 @@
 //!
 //! See <https://en.wikipedia.org/wiki/Cyclic_redundancy_check> for information.
 //!
+//! Other CRC-64 variants, e.g. CRC-64/WE, are provided by the generic engine of [crc](crate::crc).
+//!
 
-use std::ops::{Deref, DerefMut};
-
-use crate::Hash64;
+use core::fmt;
+use core::ops::Deref;
 
 /// The ECMA polynomial, defined in ECMA 182.
 pub const ECMA: u64 = 0xC96C5795D7870F42;
@@ -16,39 +17,41 @@ pub const ISO: u64 = 0xD800000000000000;
 /// The size of a CRC-64 checksum in bytes.
 pub const SIZE: usize = 8;
 
-lazy_static::lazy_static! {
-    static ref SLICING8_TABLE_ECMA: [Table;8] =  slicing8::make_table(Table::from_poly(ECMA));
+/// ECMA_TABLE is the table for the ECMA polynomial.
+pub static ECMA_TABLE: Table = Table::from_poly(ECMA);
 
-    static ref SLICING8_TABLE_ISO: [Table;8] = slicing8::make_table(Table::from_poly(ISO)) ;
-}
+/// ISO_TABLE is the table for the ISO polynomial.
+pub static ISO_TABLE: Table = Table::from_poly(ISO);
 
 /// Table is a 256-word table representing the polynomial for efficient processing.
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub struct Table(pub [u64; 256]);
+///
+/// It also carries the companion tables of the slicing-by-8 algorithm, so checksums of any polynomial are computed
+/// without building them again. This makes it 16 KiB large, so it isn't [Copy]: [digests](Digest) box it instead
+/// of holding it inline.
+#[derive(Clone)]
+pub struct Table {
+    poly: u64,
+    slicing8: Slicing8Table,
+}
 
 impl Table {
-    fn from_poly(poly: u64) -> Self {
-        let mut out = Self::default();
-
-        for i in 0usize..256 {
-            let mut crc = i as u64;
-            for _j in 0..8 {
-                crc = if crc & 1 == 1 {
-                    (crc >> 1) ^ poly
-                } else {
-                    crc >> 1
-                }
-            }
-            out[i] = crc;
+    /// from_poly builds the [Table] of the specified polynomial, like [make_table] does for the polynomials
+    /// without a prebuilt table.
+    ///
+    /// Being a const fn, it builds tables of custom polynomials at compile time, so they can be kept in statics.
+    pub const fn from_poly(poly: u64) -> Self {
+        Self {
+            poly,
+            slicing8: slicing8::make_table(poly),
         }
-
-        out
     }
 }
 
-impl Default for Table {
-    fn default() -> Self {
-        Self([0u64; 256])
+impl fmt::Debug for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Table")
+            .field("poly", &format_args!("{:#018x}", self.poly))
+            .finish_non_exhaustive()
     }
 }
 
@@ -56,16 +59,19 @@ impl Deref for Table {
     type Target = [u64];
 
     fn deref(&self) -> &Self::Target {
-        self.0.as_ref()
+        self.slicing8[0].as_ref()
     }
 }
 
-impl DerefMut for Table {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        self.0.as_mut_slice()
+// Tables of the same polynomial are always identical, so comparing polynomials is enough.
+impl PartialEq for Table {
+    fn eq(&self, other: &Self) -> bool {
+        self.poly == other.poly
     }
 }
 
+impl Eq for Table {}
+
 /// checksum returns the CRC-64 checksum of data using the polynomial represented by the [Table].
 ///
 /// # Example
@@ -76,70 +82,90 @@ pub fn checksum(data: &[u8], table: &Table) -> u64 {
     update(0, table, data)
 }
 
+/// combine returns the CRC-64 checksum of the concatenation A||B, given crc1 of A, crc2 of B and the length of B
+/// in bytes. Both checksums must be computed with the polynomial poly, e.g. [ECMA], [ISO] or any other one passed to
+/// [make_table].
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/crc64_combine.rs")]
+/// ```
+pub fn combine(crc1: u64, crc2: u64, len2: u64, poly: u64) -> u64 {
+    combine::multmodp(combine::x8nmodp(len2, poly), crc1, poly) ^ crc2
+}
+
 /// make_table returns a [Table] constructed from the specified polynomial. The contents of this Table must not be
 /// modified.
+///
+/// The tables of [ECMA] and [ISO] are copied from [ECMA_TABLE] and [ISO_TABLE] rather than computed again. Tables
+/// to be kept in statics are built at compile time by [Table::from_poly].
 pub fn make_table(poly: u64) -> Table {
     match poly {
-        ISO => SLICING8_TABLE_ISO[0],
-        ECMA => SLICING8_TABLE_ECMA[0],
+        ECMA => ECMA_TABLE.clone(),
+        ISO => ISO_TABLE.clone(),
         _ => Table::from_poly(poly),
     }
 }
 
-/// new creates a new [hash::Hash64][crate::Hash] computing the CRC-64 checksum using the polynomial represented by the
-/// [Table]. Its [sum](crate::Hash::sum) method will lay the value out in big-endian byte order.
-pub fn new(table: Table) -> Box<dyn Hash64> {
-    Box::new(digest::Digest::new(0, table))
+/// new creates a new [hash::Hash64][crate::Hash64] computing the CRC-64 checksum using the polynomial represented by the
+/// [Table]. Its [sum](crate::Hash::sum) method will lay the value out in big-endian byte order. The returned Hash64
+/// also implements [BinaryMarshaler](crate::BinaryMarshaler) and [BinaryUnmarshaler](crate::BinaryUnmarshaler) to
+/// marshal and unmarshal the internal state of the hash.
+pub fn new(table: Table) -> Digest {
+    Digest::new(0, table)
 }
 
 /// update returns the result of adding the bytes in p to the crc.
 pub fn update(crc: u64, table: &Table, p: &[u8]) -> u64 {
-    let mut crc = !crc;
-
-    let mut p = p;
-    while p.len() >= 64 {
-        let helper_table: [Table; 8] = if SLICING8_TABLE_ECMA[0].eq(table) {
-            *SLICING8_TABLE_ECMA
-        } else if SLICING8_TABLE_ISO[0].eq(table) {
-            *SLICING8_TABLE_ISO
-        } else if p.len() > 16384 {
-            slicing8::make_table(*table)
-        } else {
-            break;
-        };
-
-        while p.len() > 8 {
-            crc ^= (p[0] as u64)
-                | ((p[1] as u64) << 8)
-                | ((p[2] as u64) << 16)
-                | ((p[3] as u64) << 24)
-                | ((p[4] as u64) << 32)
-                | ((p[5] as u64) << 40)
-                | ((p[6] as u64) << 48)
-                | ((p[7] as u64) << 56);
-
-            crc = helper_table[7][(crc & 0xff) as usize]
-                ^ helper_table[6][((crc >> 8) & 0xff) as usize]
-                ^ helper_table[5][((crc >> 16) & 0xff) as usize]
-                ^ helper_table[4][((crc >> 24) & 0xff) as usize]
-                ^ helper_table[3][((crc >> 32) & 0xff) as usize]
-                ^ helper_table[2][((crc >> 40) & 0xff) as usize]
-                ^ helper_table[1][((crc >> 48) & 0xff) as usize]
-                ^ helper_table[0][((crc >> 56) & 0xff) as usize];
-
-            p = &p[8..];
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    if p.len() >= 64 {
+        let n = p.len() & !15;
+        if let Some(remainder) = fold_arch(!crc, table, &p[..n]) {
+            let crc = slicing8::update(!0, &table.slicing8, &remainder);
+            return slicing8::update(crc, &table.slicing8, &p[n..]);
         }
     }
 
-    for &v in p {
-        crc = table[((crc as u8) ^ v) as usize] ^ (crc >> 8);
+    slicing8::update(crc, &table.slicing8, p)
+}
+
+/// fold_arch folds p with the hardware-assisted carry-less multiplication if the CPU supports it, which is only
+/// available for the ECMA and ISO polynomials.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn fold_arch(crc: u64, table: &Table, p: &[u8]) -> Option<[u8; 16]> {
+    let k = match table.poly {
+        ECMA => &clmul::ECMA,
+        ISO => &clmul::ISO,
+        _ => return None,
+    };
+
+    #[cfg(target_arch = "x86_64")]
+    if amd64::available() {
+        // SAFETY: the PCLMULQDQ support has just been detected.
+        return Some(unsafe { amd64::fold(crc, k, p) });
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    if arm64::available() {
+        // SAFETY: the PMULL support has just been detected.
+        return Some(unsafe { arm64::fold(crc, k, p) });
     }
 
-    !crc
+    None
 }
 
+#[cfg(target_arch = "x86_64")]
+mod amd64;
+#[cfg(target_arch = "aarch64")]
+mod arm64;
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+mod clmul;
+mod combine;
 mod digest;
 mod slicing8;
 
+pub use digest::Digest;
+use slicing8::Slicing8Table;
+
 #[cfg(test)]
 mod tests;
@@ -1,16 +1,63 @@
-use crate::crc64::Table;
+pub const SLICING8_CUTOFF: usize = 16;
 
-pub fn make_table(t: Table) -> [Table; 8] {
-    let mut out = [Table::default(); 8];
+/// Slicing8Table holds the 8 tables of the slicing-by-8 algorithm, where the first one is the simple table.
+pub type Slicing8Table = [[u64; 256]; 8];
 
-    out[0] = t;
-    for i in 0..256 {
-        let mut crc = t[i];
-        for j in 1..8 {
-            crc = t[(crc & 0xff) as usize] ^ (crc >> 8);
-            out[j][i] = crc
+pub const fn make_table(poly: u64) -> Slicing8Table {
+    let mut out = [[0u64; 256]; 8];
+
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ poly
+            } else {
+                crc >> 1
+            };
+            j += 1;
+        }
+        out[0][i] = crc;
+        i += 1;
+    }
+
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = out[0][i];
+        let mut j = 1;
+        while j < 8 {
+            crc = out[0][(crc & 0xff) as usize] ^ (crc >> 8);
+            out[j][i] = crc;
+            j += 1;
         }
+        i += 1;
     }
 
     out
 }
+
+pub fn update(crc: u64, t: &Slicing8Table, p: &[u8]) -> u64 {
+    let (mut crc, mut p) = (!crc, p);
+
+    if p.len() >= SLICING8_CUTOFF {
+        while p.len() >= 8 {
+            crc ^= u64::from_le_bytes(p[..8].try_into().expect("&[u8] as [u8; 8]"));
+            crc = t[7][(crc & 0xff) as usize]
+                ^ t[6][((crc >> 8) & 0xff) as usize]
+                ^ t[5][((crc >> 16) & 0xff) as usize]
+                ^ t[4][((crc >> 24) & 0xff) as usize]
+                ^ t[3][((crc >> 32) & 0xff) as usize]
+                ^ t[2][((crc >> 40) & 0xff) as usize]
+                ^ t[1][((crc >> 48) & 0xff) as usize]
+                ^ t[0][((crc >> 56) & 0xff) as usize];
+            p = &p[8..];
+        }
+    }
+
+    for &v in p {
+        crc = t[0][((crc as u8) ^ v) as usize] ^ (crc >> 8);
+    }
+
+    !crc
+}
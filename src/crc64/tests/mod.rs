@@ -1,13 +1,95 @@
 use super::*;
 
+use crate::{BinaryMarshaler, BinaryUnmarshaler, Error, Hash, Hash64};
+
+#[test]
+fn combine() {
+    // 0x95ac9329ac4bc9b5 is the Jones polynomial in reversed representation
+    for poly in [ECMA, ISO, 0x95ac9329ac4bc9b5] {
+        let t = super::make_table(poly);
+
+        for g in GOLDEN_TEST_VECTOR.iter() {
+            let expect = checksum(g.input.as_bytes(), &t);
+
+            for i in 0..=g.input.len() {
+                let (a, b) = g.input.as_bytes().split_at(i);
+
+                let got = super::combine(checksum(a, &t), checksum(b, &t), b.len() as u64, poly);
+                assert_eq!(
+                    expect, got,
+                    "poly={:#016x}: combine at {} of {} = {:016x}, expect {:016x}",
+                    poly, i, g.input, got, expect
+                );
+            }
+        }
+
+        let p: Vec<u8> = (0..100000u32).map(|v| (v * 31 + v / 7) as u8).collect();
+        let (a, b) = p.split_at(54321);
+        let got = super::combine(checksum(a, &t), checksum(b, &t), b.len() as u64, poly);
+        assert_eq!(checksum(&p, &t), got, "poly={:#016x}: combine large", poly);
+    }
+}
+
+#[test]
+fn digest() {
+    let mut h = Digest::default();
+    h.update(b"hello");
+    assert_eq!(
+        checksum(b"hello", &ECMA_TABLE),
+        h.sum64(),
+        "default digest isn't ECMA"
+    );
+
+    let mut h2 = h.clone();
+    h2.update(b" world");
+    assert_eq!(
+        checksum(b"hello", &ECMA_TABLE),
+        h.sum64(),
+        "clone shares the state"
+    );
+    assert_eq!(checksum(b"hello world", &ECMA_TABLE), h2.sum64());
+
+    let got = format!("{:?}", new(make_table(ISO)));
+    assert!(got.contains("0xd800000000000000"), "{}", got);
+}
+
+#[test]
+fn cross_check() {
+    let lengths = [
+        0usize, 1, 15, 16, 63, 64, 65, 79, 80, 127, 128, 129, 255, 256, 1000, 1024, 4031, 4096,
+        10000, 20000,
+    ];
+
+    for poly in [ECMA, ISO, 0x95ac9329ac4bc9b5] {
+        let t = super::make_table(poly);
+        for n in lengths {
+            let p: Vec<u8> = (0..n).map(|v| (v * 131 + v / 3) as u8).collect();
+            let crc = 0x0123456789abcdef_u64.rotate_left(n as u32);
+
+            let mut expect = !crc;
+            for &v in p.iter() {
+                expect = t[((expect as u8) ^ v) as usize] ^ (expect >> 8);
+            }
+            expect = !expect;
+
+            let got = super::update(crc, &t, &p);
+            assert_eq!(
+                expect, got,
+                "poly={:#016x}: mismatch for buffer length {}",
+                poly, n
+            );
+        }
+    }
+}
+
 #[test]
 fn golden() {
     let table_iso = super::make_table(ISO);
     let table_ecma = super::make_table(ECMA);
 
     for (i, g) in GOLDEN_TEST_VECTOR.iter().enumerate() {
-        let mut c = super::new(table_iso);
-        let _ = c.write_all(g.input.as_bytes());
+        let mut c = super::new(table_iso.clone());
+        c.update(g.input.as_bytes());
         let s = c.sum64();
         assert_eq!(
             g.out_iso, s,
@@ -15,8 +97,8 @@ fn golden() {
             i, g.input, s, g.out_iso
         );
 
-        let mut c = super::new(table_ecma);
-        let _ = c.write_all(g.input.as_bytes());
+        let mut c = super::new(table_ecma.clone());
+        c.update(g.input.as_bytes());
         let s = c.sum64();
         assert_eq!(
             g.out_ecma, s,
@@ -26,20 +108,98 @@ fn golden() {
     }
 }
 
+#[test]
+fn golden_marshal() {
+    let table_iso = super::make_table(ISO);
+    let table_ecma = super::make_table(ECMA);
+
+    for (i, g) in GOLDEN_TEST_VECTOR.iter().enumerate() {
+        let (head, tail) = g.input.as_bytes().split_at(g.input.len() / 2);
+
+        for (name, table, half_state) in [
+            ("ISO", &table_iso, g.half_state_iso),
+            ("ECMA", &table_ecma, g.half_state_ecma),
+        ] {
+            let mut h = super::new(table.clone());
+            let mut h2 = super::new(table.clone());
+
+            h.update(head);
+
+            let state = h.marshal_binary().expect("marshal_binary");
+            assert_eq!(
+                half_state,
+                state.as_slice(),
+                "#{} {} crc64({}) state mismatch",
+                i,
+                name,
+                g.input
+            );
+
+            h2.unmarshal_binary(&state).expect("unmarshal_binary");
+
+            h.update(tail);
+            h2.update(tail);
+
+            assert_eq!(
+                h.sum64(),
+                h2.sum64(),
+                "#{} {} crc64({}) differs after restoring the state",
+                i,
+                name,
+                g.input
+            );
+        }
+    }
+}
+
+#[test]
+fn make_table_prebuilt() {
+    for (poly, table) in [(ECMA, &super::ECMA_TABLE), (ISO, &super::ISO_TABLE)] {
+        assert_eq!(&table[..], &super::make_table(poly)[..]);
+        assert_eq!(&super::Table::from_poly(poly)[..], &table[..]);
+    }
+}
+
+#[test]
+fn marshal_table_mismatch() {
+    let mut h1 = super::new(super::make_table(ISO));
+    let mut h2 = super::new(super::make_table(ECMA));
+
+    let state = h1.marshal_binary().expect("marshal_binary");
+    assert_eq!(Err(Error::TableMismatch), h2.unmarshal_binary(&state));
+
+    assert_eq!(
+        Err(Error::InvalidStateIdentifier),
+        h1.unmarshal_binary(&state[1..])
+    );
+    assert_eq!(
+        Err(Error::InvalidStateSize),
+        h1.unmarshal_binary(&state[..state.len() - 1])
+    );
+}
+
 struct Test {
     out_iso: u64,
     out_ecma: u64,
     input: &'static str,
-    //half_state_iso: &'static [u8],
-    //half_state_ecma: &'static [u8],
+    half_state_iso: &'static [u8],
+    half_state_ecma: &'static [u8],
 }
 
 impl Test {
-    fn new(out_iso: u64, out_ecma: u64, input: &'static str) -> Self {
+    fn new(
+        out_iso: u64,
+        out_ecma: u64,
+        input: &'static str,
+        half_state_iso: &'static [u8],
+        half_state_ecma: &'static [u8],
+    ) -> Self {
         Self {
             out_iso,
             out_ecma,
             input,
+            half_state_iso,
+            half_state_ecma,
         }
     }
 }
@@ -50,37 +210,37 @@ lazy_static::lazy_static! {
   // ref: https://www.sobyte.net/post/2022-07/rust-string/
   // todo: make a blog
   static ref GOLDEN_TEST_VECTOR: Vec<Test> = vec![
-Test::new(0x0, 0x0, ""),
-    Test::new(0x3420000000000000, 0x330284772e652b05, "a"),
-    Test::new(0x36c4200000000000, 0xbc6573200e84b046, "ab"),
-    Test::new(0x3776c42000000000, 0x2cd8094a1a277627, "abc"),
-    Test::new(0x336776c420000000, 0x3c9d28596e5960ba, "abcd"),
-    Test::new(0x32d36776c4200000, 0x40bdf58fb0895f2, "abcde"),
-    Test::new(0x3002d36776c42000, 0xd08e9f8545a700f4, "abcdef"),
-    Test::new(0x31b002d36776c420, 0xec20a3a8cc710e66, "abcdefg"),
-    Test::new(0xe21b002d36776c4, 0x67b4f30a647a0c59, "abcdefgh"),
-    Test::new(0x8b6e21b002d36776, 0x9966f6c89d56ef8e, "abcdefghi"),
-    Test::new(0x7f5b6e21b002d367, 0x32093a2ecd5773f4, "abcdefghij"),
-    Test::new(0x8ec0e7c835bf9cdf, 0x8a0825223ea6d221, "Discard medicine more than two years old."),
-    Test::new(0xc7db1759e2be5ab4, 0x8562c0ac2ab9a00d, "He who has a shady past knows that nice guys finish last."),
-    Test::new(0xfbf9d9603a6fa020, 0x3ee2a39c083f38b4, "I wouldn't marry him with a ten foot pole."),
-    Test::new(0xeafc4211a6daa0ef, 0x1f603830353e518a, "Free! Free!/A trip/to Mars/for 900/empty jars/Burma Shave"),
-    Test::new(0x3e05b21c7a4dc4da, 0x2fd681d7b2421fd, "The days of the digital watch are numbered.  -Tom Stoppard"),
-    Test::new(0x5255866ad6ef28a6, 0x790ef2b16a745a41, "Nepal premier won't resign."),
-    Test::new(0x8a79895be1e9c361, 0x3ef8f06daccdcddf, "For every action there is an equal and opposite government program."),
-    Test::new(0x8878963a649d4916, 0x49e41b2660b106d, "His money is twice tainted: 'taint yours and 'taint mine."),
-    Test::new(0xa7b9d53ea87eb82f, 0x561cc0cfa235ac68, "There is no reason for any individual to have a computer in their home. -Ken Olsen, 1977"),
-    Test::new(0xdb6805c0966a2f9c, 0xd4fe9ef082e69f59, "It's a tiny change to the code and not completely disgusting. - Bob Manchek"),
-    Test::new(0xf3553c65dacdadd2, 0xe3b5e46cd8d63a4d, "size:  a.out:  bad magic"),
-    Test::new(0x9d5e034087a676b9, 0x865aaf6b94f2a051, "The major problem is with sendmail.  -Mark Horton"),
-    Test::new(0xa6db2d7f8da96417, 0x7eca10d2f8136eb4, "Give me a rock, paper and scissors and I will move the world.  CCFestoon"),
-    Test::new(0x325e00cd2fe819f9, 0xd7dd118c98e98727, "If the enemy is within range, then so are you."),
-    Test::new(0x88c6600ce58ae4c6, 0x70fb33c119c29318, "It's well we cannot hear the screams/That we create in others' dreams."),
-    Test::new(0x28c4a3f3b769e078, 0x57c891e39a97d9b7, "You remind me of a TV show, but that's all right: I watch it anyway."),
-    Test::new(0xa698a34c9d9f1dca, 0xa1f46ba20ad06eb7, "C is as portable as Stonehedge!!"),
-    Test::new(0xf6c1e2a8c26c5cfc, 0x7ad25fafa1710407, "Even if I could be Shakespeare, I think I should still choose to be Faraday. - A. Huxley"),
-    Test::new(0xd402559dfe9b70c, 0x73cef1666185c13f, "The fugacity of a constituent in a mixture of gases at a given temperature is proportional to its mole fraction.  Lewis-Randall Rule"),
-    Test::new(0xdb6efff26aa94946, 0xb41858f73c389602, "How can you write a big system without C++?  -Paul Glick"),
-    Test::new(0xe7fcf1006b503b61, 0x27db187fc15bbc72, "This is a test of the emergency broadcast system."),
+Test::new(0x0, 0x0, "", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\x00\x00\x00\x00\x00\x00\x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee\x00\x00\x00\x00\x00\x00\x00\x00"),
+    Test::new(0x3420000000000000, 0x330284772e652b05, "a", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\x00\x00\x00\x00\x00\x00\x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee\x00\x00\x00\x00\x00\x00\x00\x00"),
+    Test::new(0x36c4200000000000, 0xbc6573200e84b046, "ab", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef4 \x00\x00\x00\x00\x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee3\x02\x84w.e+\x05"),
+    Test::new(0x3776c42000000000, 0x2cd8094a1a277627, "abc", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef4 \x00\x00\x00\x00\x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee3\x02\x84w.e+\x05"),
+    Test::new(0x336776c420000000, 0x3c9d28596e5960ba, "abcd", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef6\xc4 \x00\x00\x00\x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee\xbces \x0e\x84\xb0F"),
+    Test::new(0x32d36776c4200000, 0x40bdf58fb0895f2, "abcde", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef6\xc4 \x00\x00\x00\x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee\xbces \x0e\x84\xb0F"),
+    Test::new(0x3002d36776c42000, 0xd08e9f8545a700f4, "abcdef", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef7v\xc4 \x00\x00\x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee,\xd8\tJ\x1a'v'"),
+    Test::new(0x31b002d36776c420, 0xec20a3a8cc710e66, "abcdefg", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef7v\xc4 \x00\x00\x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee,\xd8\tJ\x1a'v'"),
+    Test::new(0xe21b002d36776c4, 0x67b4f30a647a0c59, "abcdefgh", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef3gv\xc4 \x00\x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee<\x9d(YnY`\xba"),
+    Test::new(0x8b6e21b002d36776, 0x9966f6c89d56ef8e, "abcdefghi", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef3gv\xc4 \x00\x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee<\x9d(YnY`\xba"),
+    Test::new(0x7f5b6e21b002d367, 0x32093a2ecd5773f4, "abcdefghij", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef2\xd3gv\xc4 \x00\x00", b"crc\x02`&\x9aR\xe1\xb7\xfee\x04\x0b\xdfX\xfb\x08\x95\xf2"),
+    Test::new(0x8ec0e7c835bf9cdf, 0x8a0825223ea6d221, "Discard medicine more than two years old.", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\xc6\xc0\x0c\xac'\x11\x12\xd5", b"crc\x02`&\x9aR\xe1\xb7\xfee\xfd%\xc0&\xa0R\xef\x95"),
+    Test::new(0xc7db1759e2be5ab4, 0x8562c0ac2ab9a00d, "He who has a shady past knows that nice guys finish last.", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\t\xcb\xd15X[r\t", b"crc\x02`&\x9aR\xe1\xb7\xfee\x07\x02\xe8|+\xc1\x06\xe3"),
+    Test::new(0xfbf9d9603a6fa020, 0x3ee2a39c083f38b4, "I wouldn't marry him with a ten foot pole.", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\x19\xc8d\xbe\x84\x14\x87_", b"crc\x02`&\x9aR\xe1\xb7\xfee\xcb\xb7\xd3\xeeG\xdcE\x8c"),
+    Test::new(0xeafc4211a6daa0ef, 0x1f603830353e518a, "Free! Free!/A trip/to Mars/for 900/empty jars/Burma Shave", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\xad\x1b*\xc0\xb1\xf3i(", b"crc\x02`&\x9aR\xe1\xb7\xfee\xa7\x8a\xdb\xf6\xd2R\t\x96"),
+    Test::new(0x3e05b21c7a4dc4da, 0x2fd681d7b2421fd, "The days of the digital watch are numbered.  -Tom Stoppard", b"crc\x02s\xba\x84\x84\xbb\xcd]\xefv78\x1ak\x02\x8f\xff", b"crc\x02`&\x9aR\xe1\xb7\xfeeT\xcbl\x10\xfb\x87K*"),
+    Test::new(0x5255866ad6ef28a6, 0x790ef2b16a745a41, "Nepal premier won't resign.", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\xcbf\x11R\xbfh\xde\xc9", b"crc\x02`&\x9aR\xe1\xb7\xfee6\x13\xd9\x8f\x06_\xbd\x9a"),
+    Test::new(0x8a79895be1e9c361, 0x3ef8f06daccdcddf, "For every action there is an equal and opposite government program.", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\xf3pV\x01c_Wu", b"crc\x02`&\x9aR\xe1\xb7\xfee\xe7\xc6\n\x08\x12FL\xa0"),
+    Test::new(0x8878963a649d4916, 0x49e41b2660b106d, "His money is twice tainted: 'taint yours and 'taint mine.", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\xc3\xb1\xff\xf1\xe0/\xce\x94", b"crc\x02`&\x9aR\xe1\xb7\xfeeOL/\xb1\xec\xa2\x14\x87"),
+    Test::new(0xa7b9d53ea87eb82f, 0x561cc0cfa235ac68, "There is no reason for any individual to have a computer in their home. -Ken Olsen, 1977", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\xdd\xb8a\xe1\xb5\xf8\xb9W", b"crc\x02`&\x9aR\xe1\xb7\xfee\x87)GQ\x03\xf4K\t"),
+    Test::new(0xdb6805c0966a2f9c, 0xd4fe9ef082e69f59, "It's a tiny change to the code and not completely disgusting. - Bob Manchek", b"crc\x02s\xba\x84\x84\xbb\xcd]\xefV\xba\x12\x91\x81\x1fNU", b"crc\x02`&\x9aR\xe1\xb7\xfee\n\xb8\x81v?\xdeL\xcb"),
+    Test::new(0xf3553c65dacdadd2, 0xe3b5e46cd8d63a4d, "size:  a.out:  bad magic", b"crc\x02s\xba\x84\x84\xbb\xcd]\xefG\xad\xbc\xb2\xa8y\xc9\xdc", b"crc\x02`&\x9aR\xe1\xb7\xfee\xcc\xce\xe5\xe6\x89p\x01\xb8"),
+    Test::new(0x9d5e034087a676b9, 0x865aaf6b94f2a051, "The major problem is with sendmail.  -Mark Horton", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\xa2\xacn\x8aT;&\xd5", b"crc\x02`&\x9aR\xe1\xb7\xfeeFf\x9c\x1f\xc9x\xbfa"),
+    Test::new(0xa6db2d7f8da96417, 0x7eca10d2f8136eb4, "Give me a rock, paper and scissors and I will move the world.  CCFestoon", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\xeb\x18\xbf\xf9}\x91\xe5|", b"crc\x02`&\x9aR\xe1\xb7\xfeea\x9e\x05:\xce[\xe7\x19"),
+    Test::new(0x325e00cd2fe819f9, 0xd7dd118c98e98727, "If the enemy is within range, then so are you.", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef^5k\xd0Aj_{", b"crc\x02`&\x9aR\xe1\xb7\xfee\x0b#\x99\xa8r\x83YR"),
+    Test::new(0x88c6600ce58ae4c6, 0x70fb33c119c29318, "It's well we cannot hear the screams/That we create in others' dreams.", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef|\xb5\x02\xdcw\x18/\x86", b"crc\x02`&\x9aR\xe1\xb7\xfee]\x9d-\xed\x8c\xf9r9"),
+    Test::new(0x28c4a3f3b769e078, 0x57c891e39a97d9b7, "You remind me of a TV show, but that's all right: I watch it anyway.", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\x03\x8bd\x1c\xb0_\x16\x98", b"crc\x02`&\x9aR\xe1\xb7\xfee\xafW\x98\xaa\"\xe7\xd7|"),
+    Test::new(0xa698a34c9d9f1dca, 0xa1f46ba20ad06eb7, "C is as portable as Stonehedge!!", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef.P\xe1I\xc6pi\xdc", b"crc\x02`&\x9aR\xe1\xb7\xfee\xd6\x9a\x06\x01(\xc0\x1e\x8b"),
+    Test::new(0xf6c1e2a8c26c5cfc, 0x7ad25fafa1710407, "Even if I could be Shakespeare, I think I should still choose to be Faraday. - A. Huxley", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\xf7\xa04\x8a\xf2o\xe0;", b"crc\x02`&\x9aR\xe1\xb7\xfee<[\xd2%\x9em\x94\x04"),
+    Test::new(0xd402559dfe9b70c, 0x73cef1666185c13f, "The fugacity of a constituent in a mixture of gases at a given temperature is proportional to its mole fraction.  Lewis-Randall Rule", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef\x7f\xae\xb9\xbaX=\x19v", b"crc\x02`&\x9aR\xe1\xb7\xfee\xb2\xcb\xa6Y\xc5\xd0G\x03"),
+    Test::new(0xdb6efff26aa94946, 0xb41858f73c389602, "How can you write a big system without C++?  -Paul Glick", b"crc\x02s\xba\x84\x84\xbb\xcd]\xefa\xed$js\xb9\xa5A", b"crc\x02`&\x9aR\xe1\xb7\xfeeZm\x96\x8a\xe2\xaf\x13p"),
+    Test::new(0xe7fcf1006b503b61, 0x27db187fc15bbc72, "This is a test of the emergency broadcast system.", b"crc\x02s\xba\x84\x84\xbb\xcd]\xef}\xee[q\x16\xcb\xe4\x8d", b"crc\x02`&\x9aR\xe1\xb7\xfee\xb1\x93] \xeb\xa9am"),
   ];
 }
@@ -0,0 +1,61 @@
+//! Module crc8 implements the 8-bit cyclic redundancy check, or CRC-8, checksum.
+//!
+//! See <https://en.wikipedia.org/wiki/Cyclic_redundancy_check> for information.
+//!
+//! The CRC-8 variants used by sensors and buses differ in the polynomial, the initial value and the bit order, so
+//! each preset is an [Algorithm] of the [crc] engine, whose polynomial is in normal (MSB-first)
+//! representation.
+//!
+
+use crate::crc::{self, Algorithm, Crc};
+
+/// AUTOSAR is used by the end-to-end protection profiles of AUTOSAR.
+pub const AUTOSAR: Algorithm<u8> = crc::CRC_8_AUTOSAR;
+
+/// CDMA2000 is used by the CDMA2000 mobile networks.
+pub const CDMA2000: Algorithm<u8> = crc::CRC_8_CDMA2000;
+
+/// DVB_S2 is used by the DVB-S2 satellite broadcasting.
+pub const DVB_S2: Algorithm<u8> = crc::CRC_8_DVB_S2;
+
+/// MAXIM, catalogued as CRC-8/MAXIM-DOW, used by the Dallas/Maxim 1-Wire bus.
+pub const MAXIM: Algorithm<u8> = crc::CRC_8_MAXIM_DOW;
+
+/// SMBUS is used by the packet error checking of the System Management Bus.
+pub const SMBUS: Algorithm<u8> = crc::CRC_8_SMBUS;
+
+/// The size of a CRC-8 checksum in bytes.
+pub const SIZE: usize = 1;
+
+/// Table is the [Crc] engine of a CRC-8 algorithm, holding its precomputed 256-entry table.
+pub type Table = Crc<u8>;
+
+/// Digest computes the CRC-8 checksum incrementally, as returned by [new].
+pub type Digest = crc::Digest<'static, u8>;
+
+/// checksum returns the CRC-8 checksum of data using the algorithm represented by the Table.
+pub fn checksum(data: &[u8], table: &Table) -> u8 {
+    table.checksum(data)
+}
+
+/// make_table returns a Table constructed from the specified algorithm, e.g. one of the presets of this module.
+///
+/// # Panics
+/// It panics if the width of the algorithm isn't in 1..=8.
+pub fn make_table(algorithm: Algorithm<u8>) -> Table {
+    Crc::new(algorithm)
+}
+
+/// new creates a new [`Hash8`](crate::Hash8) computing the CRC-8 checksum using the algorithm represented by the
+/// Table. Its [`sum`](crate::Hash::sum) method will append the checksum as a single byte.
+pub fn new(t: Table) -> Digest {
+    t.into_digest()
+}
+
+/// update returns the result of adding the bytes in p to the crc.
+pub fn update(crc: u8, t: &Table, p: &[u8]) -> u8 {
+    t.update(crc, p)
+}
+
+#[cfg(test)]
+mod tests;
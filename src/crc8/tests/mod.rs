@@ -0,0 +1,92 @@
+use super::*;
+use crate::{Hash, Hash8};
+
+struct Test {
+    autosar: u8,
+    cdma2000: u8,
+    dvb_s2: u8,
+    maxim: u8,
+    smbus: u8,
+    input: &'static [u8],
+}
+
+#[test]
+fn golden() {
+    let presets = [AUTOSAR, CDMA2000, DVB_S2, MAXIM, SMBUS];
+
+    for g in GOLDEN_TEST_VECTOR.iter() {
+        let expect = [g.autosar, g.cdma2000, g.dvb_s2, g.maxim, g.smbus];
+
+        for (alg, expect) in presets.iter().zip(expect) {
+            let t = make_table(*alg);
+            assert_eq!(expect, checksum(g.input, &t), "{:?}({:?})", alg, g.input);
+
+            for delta in 0..=g.input.len() {
+                let (a, b) = g.input.split_at(delta);
+
+                let got = update(checksum(a, &t), &t, b);
+                assert_eq!(
+                    expect, got,
+                    "{:?}: update at {} of {:?}",
+                    alg, delta, g.input
+                );
+
+                let mut h = new(t);
+                h.update(a);
+                h.update(b);
+                assert_eq!(
+                    expect,
+                    h.sum8(),
+                    "{:?}: write at {} of {:?}",
+                    alg,
+                    delta,
+                    g.input
+                );
+                assert_eq!(vec![expect], h.sum(None));
+            }
+        }
+    }
+}
+
+const GOLDEN_TEST_VECTOR: &[Test] = &[
+    Test {
+        autosar: 0x00,
+        cdma2000: 0xff,
+        dvb_s2: 0x00,
+        maxim: 0x00,
+        smbus: 0x00,
+        input: b"",
+    },
+    Test {
+        autosar: 0x07,
+        cdma2000: 0x4c,
+        dvb_s2: 0xec,
+        maxim: 0x3b,
+        smbus: 0x20,
+        input: b"a",
+    },
+    Test {
+        autosar: 0x41,
+        cdma2000: 0x33,
+        dvb_s2: 0x5a,
+        maxim: 0x42,
+        smbus: 0x5f,
+        input: b"abc",
+    },
+    Test {
+        autosar: 0xdf,
+        cdma2000: 0xda,
+        dvb_s2: 0xbc,
+        maxim: 0xa1,
+        smbus: 0xf4,
+        input: b"123456789",
+    },
+    Test {
+        autosar: 0x67,
+        cdma2000: 0x02,
+        dvb_s2: 0x2a,
+        maxim: 0x16,
+        smbus: 0xc1,
+        input: b"The quick brown fox jumps over the lazy dog",
+    },
+];
@@ -4,42 +4,52 @@
 //! See
 //! <https://en.wikipedia.org/wiki/Fowler-Noll-Vo_hash_function>.
 //!
+//! All the hashes returned by this module also implement [BinaryMarshaler](crate::BinaryMarshaler) and
+//! [BinaryUnmarshaler](crate::BinaryUnmarshaler) to marshal and
+//! unmarshal their internal states.
+//!
+//! They implement [`core::hash::Hasher`] as well, so they can back a `HashMap` or `HashSet`, e.g. through
+//! [BuildHasher64a], [FnvHashMap] and [FnvHashSet]. [finish](core::hash::Hasher::finish) returns the 32-bit
+//! sums zero-extended and the lower 64 bits of the 128-bit sums.
+//!
+
+use alloc::vec::Vec;
 
-use crate::{Hash, Hash32, Hash64};
+use crate::Error;
 
-/// new128 returns a new 128-bit FNV-1 [Hash][crate::Hash].
+/// new128 returns a new 128-bit FNV-1 [Hash128][crate::Hash128].
 /// Its [sum][crate::Hash::sum] method will lay the value out in big-endian byte order.
-pub fn new128() -> impl Hash {
+pub fn new128() -> Sum128 {
     Sum128::new()
 }
 
-/// new128a returns a new 128-bit FNV-1a [Hash][crate::Hash].
+/// new128a returns a new 128-bit FNV-1a [Hash128][crate::Hash128].
 /// Its [sum][crate::Hash::sum] method will lay the value out in big-endian byte order.
-pub fn new128a() -> impl Hash {
+pub fn new128a() -> Sum128a {
     Sum128a::new()
 }
 
 /// new32 returns a new 32-bit FNV-1 [Hash][crate::Hash].
 /// Its [sum][crate::Hash::sum] method will lay the value out in big-endian byte order.
-pub fn new32() -> impl Hash32 {
+pub fn new32() -> Sum32 {
     Sum32::new()
 }
 
 /// new32a returns a new 32-bit FNV-1a [Hash][crate::Hash].
 /// Its [sum][crate::Hash::sum] method will lay the value out in big-endian byte order.
-pub fn new32a() -> impl Hash32 {
+pub fn new32a() -> Sum32a {
     Sum32a::new()
 }
 
 /// new64 returns a new 64-bit FNV-1 [Hash][crate::Hash].
 /// Its [sum][crate::Hash::sum] method will lay the value out in big-endian byte order.
-pub fn new64() -> impl Hash64 {
+pub fn new64() -> Sum64 {
     Sum64::new()
 }
 
 /// new64a returns a new 64-bit FNV-1a [Hash][crate::Hash].
 /// Its [sum][crate::Hash::sum] method will lay the value out in big-endian byte order.
-pub fn new64a() -> impl Hash64 {
+pub fn new64a() -> Sum64a {
     Sum64a::new()
 }
 
@@ -52,6 +62,96 @@ const PRIME64: u64 = 1099511628211;
 const PRIME128_LOWER: u128 = 0x013b;
 const PRIME128_SHIFT: u32 = 24;
 
+const MAGIC32: &[u8] = b"fnv\x01";
+const MAGIC32A: &[u8] = b"fnv\x02";
+const MAGIC64: &[u8] = b"fnv\x03";
+const MAGIC64A: &[u8] = b"fnv\x04";
+const MAGIC128: &[u8] = b"fnv\x05";
+const MAGIC128A: &[u8] = b"fnv\x06";
+const MARSHALED_SIZE32: usize = MAGIC32.len() + 4;
+const MARSHALED_SIZE64: usize = MAGIC64.len() + 8;
+const MARSHALED_SIZE128: usize = MAGIC128.len() + 8 * 2;
+
+/// BuildHasher64a builds [Sum64a] hashers, to be used as the hasher of a `HashMap` or `HashSet`.
+pub type BuildHasher64a = core::hash::BuildHasherDefault<Sum64a>;
+
+/// FnvHashMap is a [`HashMap`](std::collections::HashMap) using the 64-bit FNV-1a hash.
+#[cfg(feature = "std")]
+pub type FnvHashMap<K, V> = std::collections::HashMap<K, V, BuildHasher64a>;
+
+/// FnvHashSet is a [`HashSet`](std::collections::HashSet) using the 64-bit FNV-1a hash.
+#[cfg(feature = "std")]
+pub type FnvHashSet<T> = std::collections::HashSet<T, BuildHasher64a>;
+
+/// impl_hasher implements [`core::hash::Hasher`] by forwarding to [Hash::update](crate::Hash::update), with
+/// `finish` evaluating the given expression on the hash.
+///
+/// The fixed-size writes feed the native-endian bytes of the integer like the default methods do, but
+/// without going through a slice of unknown length.
+macro_rules! impl_hasher {
+    ($t:ty, |$h:ident| $finish:expr) => {
+        impl core::hash::Hasher for $t {
+            #[inline]
+            fn finish(&self) -> u64 {
+                let $h = self;
+                $finish
+            }
+
+            #[inline]
+            fn write(&mut self, bytes: &[u8]) {
+                crate::Hash::update(self, bytes);
+            }
+
+            #[inline]
+            fn write_u8(&mut self, i: u8) {
+                crate::Hash::update(self, &[i]);
+            }
+
+            #[inline]
+            fn write_u16(&mut self, i: u16) {
+                crate::Hash::update(self, &i.to_ne_bytes());
+            }
+
+            #[inline]
+            fn write_u32(&mut self, i: u32) {
+                crate::Hash::update(self, &i.to_ne_bytes());
+            }
+
+            #[inline]
+            fn write_u64(&mut self, i: u64) {
+                crate::Hash::update(self, &i.to_ne_bytes());
+            }
+
+            #[inline]
+            fn write_usize(&mut self, i: usize) {
+                crate::Hash::update(self, &i.to_ne_bytes());
+            }
+        }
+    };
+}
+
+use impl_hasher;
+
+/// marshal returns the state s prefixed by magic.
+fn marshal(magic: &[u8], s: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(magic.len() + s.len());
+    out.extend_from_slice(magic);
+    out.extend_from_slice(s);
+    out
+}
+
+/// unmarshal validates data against magic and the expected size, and returns the state following magic.
+fn unmarshal<'a>(data: &'a [u8], magic: &[u8], size: usize) -> Result<&'a [u8], Error> {
+    if !data.starts_with(magic) {
+        return Err(Error::InvalidStateIdentifier);
+    }
+    if data.len() != size {
+        return Err(Error::InvalidStateSize);
+    }
+
+    Ok(&data[magic.len()..])
+}
+
 mod sum128;
 mod sum128a;
 mod sum32;
@@ -59,12 +159,12 @@ mod sum32a;
 mod sum64;
 mod sum64a;
 
-use sum128::Sum128;
-use sum128a::Sum128a;
-use sum32::Sum32;
-use sum32a::Sum32a;
-use sum64::Sum64;
-use sum64a::Sum64a;
+pub use sum128::Sum128;
+pub use sum128a::Sum128a;
+pub use sum32::Sum32;
+pub use sum32a::Sum32a;
+pub use sum64::Sum64;
+pub use sum64a::Sum64a;
 
 #[cfg(test)]
 mod tests;
@@ -1,48 +1,31 @@
-use std::io::Write;
+use alloc::vec::Vec;
 
-use crate::fnv::{OFFSET128_HIGHER, OFFSET128_LOWER};
-use crate::Hash;
+use crate::fnv::{self, MAGIC128A, MARSHALED_SIZE128, OFFSET128_HIGHER, OFFSET128_LOWER};
+use crate::{BinaryMarshaler, BinaryUnmarshaler, Error, Hash, Hash128};
 
 use super::{PRIME128_LOWER, PRIME128_SHIFT};
 
+/// Sum128a is the 128-bit FNV-1a hash, as returned by [new128a](crate::fnv::new128a).
+#[derive(Clone, Debug)]
 pub struct Sum128a(u64, u64);
 
 impl Sum128a {
+    /// new returns a new 128-bit FNV-1a hash.
     pub fn new() -> Self {
         Self(OFFSET128_HIGHER, OFFSET128_LOWER)
     }
 }
 
-impl Hash for Sum128a {
-    fn sum(&mut self, b: Option<Vec<u8>>) -> Vec<u8> {
-        let (s0, s1) = (self.0.to_be_bytes(), self.1.to_be_bytes());
-        match b {
-            Some(mut v) => {
-                v.extend_from_slice(&s0);
-                v.extend_from_slice(&s1);
-                v
-            }
-            None => [s0, s1].concat().to_vec(),
-        }
-    }
-
-    fn reset(&mut self) {
-        self.0 = OFFSET128_HIGHER;
-        self.1 = OFFSET128_LOWER;
-    }
-
-    fn size(&self) -> usize {
-        16
-    }
-
-    fn block_size(&self) -> isize {
-        1
+impl Default for Sum128a {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl Write for Sum128a {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        for &v in buf {
+impl Hash for Sum128a {
+    #[inline]
+    fn update(&mut self, p: &[u8]) {
+        for &v in p {
             self.1 ^= v as u64;
 
             let (mut s0, s1) = {
@@ -58,11 +41,50 @@ impl Write for Sum128a {
             self.1 = s1;
             self.0 = s0;
         }
+    }
 
-        Ok(buf.len())
+    fn sum_into(&self, out: &mut [u8]) {
+        out[..8].copy_from_slice(&self.0.to_be_bytes());
+        out[8..16].copy_from_slice(&self.1.to_be_bytes());
     }
 
-    fn flush(&mut self) -> std::io::Result<()> {
+    fn reset(&mut self) {
+        self.0 = OFFSET128_HIGHER;
+        self.1 = OFFSET128_LOWER;
+    }
+
+    fn size(&self) -> usize {
+        16
+    }
+
+    fn block_size(&self) -> isize {
+        1
+    }
+}
+
+impl Hash128 for Sum128a {
+    fn sum128(&self) -> u128 {
+        ((self.0 as u128) << 64) | (self.1 as u128)
+    }
+}
+
+impl BinaryMarshaler for Sum128a {
+    fn marshal_binary(&self) -> Result<Vec<u8>, Error> {
+        let s = [self.0.to_be_bytes(), self.1.to_be_bytes()].concat();
+        Ok(fnv::marshal(MAGIC128A, &s))
+    }
+}
+
+impl BinaryUnmarshaler for Sum128a {
+    fn unmarshal_binary(&mut self, data: &[u8]) -> Result<(), Error> {
+        let s = fnv::unmarshal(data, MAGIC128A, MARSHALED_SIZE128)?;
+        let (s0, s1) = s.split_at(8);
+        self.0 = u64::from_be_bytes(s0.try_into().expect("state as [u8; 8]"));
+        self.1 = u64::from_be_bytes(s1.try_into().expect("state as [u8; 8]"));
         Ok(())
     }
 }
+
+crate::impl_write!(Sum128a);
+
+fnv::impl_hasher!(Sum128a, |h| h.1);
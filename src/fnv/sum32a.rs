@@ -1,26 +1,39 @@
-use std::io::Write;
+use alloc::vec::Vec;
 
-use crate::fnv::{OFFSET32, PRIME32};
-use crate::{Hash, Hash32};
+use crate::fnv::{self, MAGIC32A, MARSHALED_SIZE32, OFFSET32, PRIME32};
+use crate::{BinaryMarshaler, BinaryUnmarshaler, Error, Hash, Hash32};
 
+/// Sum32a is the 32-bit FNV-1a hash, as returned by [new32a](crate::fnv::new32a).
+#[derive(Clone, Debug)]
 pub struct Sum32a(u32);
 
 impl Sum32a {
+    /// new returns a new 32-bit FNV-1a hash.
     pub fn new() -> Self {
         Self(OFFSET32)
     }
 }
 
+impl Default for Sum32a {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Hash for Sum32a {
-    fn sum(&mut self, b: Option<Vec<u8>>) -> Vec<u8> {
-        let s = self.0.to_be_bytes();
-        match b {
-            Some(mut v) => {
-                v.extend_from_slice(&s);
-                v
-            }
-            None => s.to_vec(),
+    #[inline]
+    fn update(&mut self, p: &[u8]) {
+        let mut h = self.0;
+        for &v in p {
+            h ^= v as u32;
+            h = h.wrapping_mul(PRIME32);
         }
+        self.0 = h;
+    }
+
+    fn sum_into(&self, out: &mut [u8]) {
+        let s = self.0.to_be_bytes();
+        out[..s.len()].copy_from_slice(&s);
     }
 
     fn reset(&mut self) {
@@ -37,24 +50,25 @@ impl Hash for Sum32a {
 }
 
 impl Hash32 for Sum32a {
-    fn sum32(&mut self) -> u32 {
+    fn sum32(&self) -> u32 {
         self.0
     }
 }
 
-impl Write for Sum32a {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let mut h = self.0;
-        for &v in buf {
-            h ^= v as u32;
-            h = h.wrapping_mul(PRIME32);
-        }
-        self.0 = h;
-
-        Ok(buf.len())
+impl BinaryMarshaler for Sum32a {
+    fn marshal_binary(&self) -> Result<Vec<u8>, Error> {
+        Ok(fnv::marshal(MAGIC32A, &self.0.to_be_bytes()))
     }
+}
 
-    fn flush(&mut self) -> std::io::Result<()> {
+impl BinaryUnmarshaler for Sum32a {
+    fn unmarshal_binary(&mut self, data: &[u8]) -> Result<(), Error> {
+        let s = fnv::unmarshal(data, MAGIC32A, MARSHALED_SIZE32)?;
+        self.0 = u32::from_be_bytes(s.try_into().expect("state as [u8; 4]"));
         Ok(())
     }
 }
+
+crate::impl_write!(Sum32a);
+
+fnv::impl_hasher!(Sum32a, |h| h.0 as u64);
@@ -1,26 +1,39 @@
-use std::io::Write;
+use alloc::vec::Vec;
 
-use crate::fnv::{OFFSET64, PRIME64};
-use crate::{Hash, Hash64};
+use crate::fnv::{self, MAGIC64A, MARSHALED_SIZE64, OFFSET64, PRIME64};
+use crate::{BinaryMarshaler, BinaryUnmarshaler, Error, Hash, Hash64};
 
+/// Sum64a is the 64-bit FNV-1a hash, as returned by [new64a](crate::fnv::new64a).
+#[derive(Clone, Debug)]
 pub struct Sum64a(u64);
 
 impl Sum64a {
+    /// new returns a new 64-bit FNV-1a hash.
     pub fn new() -> Self {
         Self(OFFSET64)
     }
 }
 
+impl Default for Sum64a {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Hash for Sum64a {
-    fn sum(&mut self, b: Option<Vec<u8>>) -> Vec<u8> {
-        let s = self.0.to_be_bytes();
-        match b {
-            Some(mut v) => {
-                v.extend_from_slice(&s);
-                v
-            }
-            None => s.to_vec(),
+    #[inline]
+    fn update(&mut self, p: &[u8]) {
+        let mut h = self.0;
+        for &v in p {
+            h ^= v as u64;
+            h = h.wrapping_mul(PRIME64);
         }
+        self.0 = h;
+    }
+
+    fn sum_into(&self, out: &mut [u8]) {
+        let s = self.0.to_be_bytes();
+        out[..s.len()].copy_from_slice(&s);
     }
 
     fn reset(&mut self) {
@@ -37,24 +50,25 @@ impl Hash for Sum64a {
 }
 
 impl Hash64 for Sum64a {
-    fn sum64(&mut self) -> u64 {
+    fn sum64(&self) -> u64 {
         self.0
     }
 }
 
-impl Write for Sum64a {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let mut h = self.0;
-        for &v in buf {
-            h ^= v as u64;
-            h = h.wrapping_mul(PRIME64);
-        }
-        self.0 = h;
-
-        Ok(buf.len())
+impl BinaryMarshaler for Sum64a {
+    fn marshal_binary(&self) -> Result<Vec<u8>, Error> {
+        Ok(fnv::marshal(MAGIC64A, &self.0.to_be_bytes()))
     }
+}
 
-    fn flush(&mut self) -> std::io::Result<()> {
+impl BinaryUnmarshaler for Sum64a {
+    fn unmarshal_binary(&mut self, data: &[u8]) -> Result<(), Error> {
+        let s = fnv::unmarshal(data, MAGIC64A, MARSHALED_SIZE64)?;
+        self.0 = u64::from_be_bytes(s.try_into().expect("state as [u8; 8]"));
         Ok(())
     }
 }
+
+crate::impl_write!(Sum64a);
+
+fnv::impl_hasher!(Sum64a, |h| h.0);
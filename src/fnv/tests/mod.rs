@@ -1,4 +1,8 @@
-use crate::{Hash, Hash32, Hash64};
+use core::hash::Hasher;
+
+use crate::{BinaryMarshaler, BinaryUnmarshaler, Error, Hash, Hash128, Hash32, Hash64};
+
+use super::{MAGIC128, MAGIC128A, MAGIC32, MAGIC32A, MAGIC64, MAGIC64A};
 
 #[test]
 fn golden128() {
@@ -30,14 +34,117 @@ fn golden64a() {
     test_golden(super::new64a(), GOLDEN64A_TEST_VECTOR.as_slice());
 }
 
+#[test]
+fn golden_marshal128() {
+    test_golden_marshal(super::new128, MAGIC128, GOLDEN128_TEST_VECTOR.as_slice());
+}
+
+#[test]
+fn golden_marshal128a() {
+    test_golden_marshal(super::new128a, MAGIC128A, GOLDEN128A_TEST_VECTOR.as_slice());
+}
+
+#[test]
+fn golden_marshal32() {
+    test_golden_marshal(super::new32, MAGIC32, GOLDEN32_TEST_VECTOR.as_slice());
+}
+
+#[test]
+fn golden_marshal32a() {
+    test_golden_marshal(super::new32a, MAGIC32A, GOLDEN32A_TEST_VECTOR.as_slice());
+}
+
+#[test]
+fn golden_marshal64() {
+    test_golden_marshal(super::new64, MAGIC64, GOLDEN64_TEST_VECTOR.as_slice());
+}
+
+#[test]
+fn golden_marshal64a() {
+    test_golden_marshal(super::new64a, MAGIC64A, GOLDEN64A_TEST_VECTOR.as_slice());
+}
+
+#[test]
+fn unmarshal_bad_state() {
+    let state = super::new32().marshal_binary().expect("marshal_binary");
+
+    assert_eq!(
+        Err(Error::InvalidStateIdentifier),
+        super::new32a().unmarshal_binary(&state)
+    );
+    assert_eq!(
+        Err(Error::InvalidStateSize),
+        super::new32().unmarshal_binary(&state[..state.len() - 1])
+    );
+}
+
+#[test]
+fn default_clone() {
+    test_default_clone::<super::Sum128>();
+    test_default_clone::<super::Sum128a>();
+    test_default_clone::<super::Sum32>();
+    test_default_clone::<super::Sum32a>();
+    test_default_clone::<super::Sum64>();
+    test_default_clone::<super::Sum64a>();
+}
+
+#[test]
+fn hasher() {
+    test_hasher::<super::Sum128>();
+    test_hasher::<super::Sum128a>();
+    test_hasher::<super::Sum32>();
+    test_hasher::<super::Sum32a>();
+    test_hasher::<super::Sum64>();
+    test_hasher::<super::Sum64a>();
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn hash_map() {
+    use core::hash::BuildHasher;
+
+    let mut m = super::FnvHashMap::default();
+    m.insert(1u32, "one");
+    m.insert(2u32, "two");
+    assert_eq!(Some(&"one"), m.get(&1));
+    assert_eq!(None, m.get(&3));
+
+    let mut s = super::FnvHashSet::default();
+    assert!(s.insert("hello"));
+    assert!(!s.insert("hello"));
+    assert!(s.contains("hello"));
+
+    let b = super::BuildHasher64a::default();
+    let mut h = b.build_hasher();
+    Hasher::write(&mut h, b"abc");
+    assert_eq!(0xe71fa2190541574b, h.finish());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn io_write() {
+    use std::io::Write;
+
+    let data = b"hello, world";
+
+    let mut h = super::new64a();
+    let done = Write::write(&mut h, data).expect("write");
+    assert_eq!(data.len(), done, "wrong #(byte) written");
+    h.flush().expect("flush");
+
+    let mut h2 = super::new64a();
+    h2.update(data);
+    assert_eq!(h2.sum64(), h.sum64(), "write differs from update");
+}
+
 #[test]
 fn integrity128() {
-    test_integrity(super::new128());
+    test_integrity128(super::new128());
 }
 
 #[test]
 fn integrity128a() {
-    test_integrity(super::new128a());
+    test_integrity128(super::new128a());
 }
 
 #[test]
@@ -68,14 +175,96 @@ where
     for g in test_vector.iter().skip(1) {
         h.reset();
 
-        let done = h.write(g.input.as_bytes()).expect("write");
-        assert_eq!(g.input.len(), done, "wrong #(byte) written");
+        h.update(g.input.as_bytes());
 
         let got = h.sum(None);
         assert_eq!(g.out, got.as_slice(), "hash({})", g.input);
     }
 }
 
+fn test_golden_marshal<H, F>(new_hash: F, magic: &[u8], test_vector: &[Test])
+where
+    H: Hash + BinaryMarshaler + BinaryUnmarshaler,
+    F: Fn() -> H,
+{
+    for g in test_vector.iter() {
+        let (head, tail) = g.input.as_bytes().split_at(g.input.len() / 2);
+
+        let mut h = new_hash();
+        let mut h2 = new_hash();
+
+        h.update(head);
+
+        let state = h.marshal_binary().expect("marshal_binary");
+        let expect = [magic, h.sum(None).as_slice()].concat();
+        assert_eq!(expect, state, "hash({}) state mismatch", g.input);
+
+        h2.unmarshal_binary(&state).expect("unmarshal_binary");
+
+        h.update(tail);
+        h2.update(tail);
+
+        assert_eq!(g.out, h.sum(None).as_slice(), "hash({})", g.input);
+        assert_eq!(
+            g.out,
+            h2.sum(None).as_slice(),
+            "hash({}) after restoring the state",
+            g.input
+        );
+    }
+}
+
+fn test_default_clone<H>()
+where
+    H: Hash + Clone + Default + std::fmt::Debug,
+{
+    let mut h = H::default();
+    h.update(b"hello");
+
+    let mut h2 = h.clone();
+    assert_eq!(h.sum(None), h2.sum(None), "clone differs");
+
+    h2.update(b" world");
+    assert_ne!(h.sum(None), h2.sum(None), "clone shares the state");
+
+    h.update(b" world");
+    assert_eq!(h.sum(None), h2.sum(None), "clone diverges");
+    assert_eq!(format!("{:?}", h), format!("{:?}", h2));
+}
+
+fn test_hasher<H>()
+where
+    H: Hash + Hasher + Default,
+{
+    for g in GOLDEN32_TEST_VECTOR.iter() {
+        let mut h = H::default();
+        Hasher::write(&mut h, g.input.as_bytes());
+
+        let sum = h.sum(None);
+        let mut want = [0u8; 8];
+        let n = sum.len().min(want.len());
+        want[8 - n..].copy_from_slice(&sum[sum.len() - n..]);
+        assert_eq!(u64::from_be_bytes(want), h.finish(), "finish({})", g.input);
+    }
+
+    let mut fast = H::default();
+    fast.write_u8(0x01);
+    fast.write_u16(0x0203);
+    fast.write_u32(0x04050607);
+    fast.write_u64(0x08090a0b0c0d0e0f);
+    fast.write_usize(0x10);
+
+    let mut slow = H::default();
+    Hasher::write(&mut slow, &0x01u8.to_ne_bytes());
+    Hasher::write(&mut slow, &0x0203u16.to_ne_bytes());
+    Hasher::write(&mut slow, &0x04050607u32.to_ne_bytes());
+    Hasher::write(&mut slow, &0x08090a0b0c0d0e0fu64.to_ne_bytes());
+    Hasher::write(&mut slow, &0x10usize.to_ne_bytes());
+
+    assert_eq!(slow.finish(), fast.finish());
+    assert_eq!(slow.sum(None), fast.sum(None));
+}
+
 fn test_integrity<H>(h: H)
 where
     H: Hash,
@@ -84,27 +273,49 @@ where
 
     let data = &[b'1', b'2', 3, 4, 5];
 
-    let _ = h.write(data).unwrap();
+    h.update(data);
     let sum = h.sum(None);
 
-    assert_eq!(h.size() as usize, sum.len(), "bad output size");
+    assert_eq!(h.size(), sum.len(), "bad output size");
+
+    let mut out = [0u8; 16];
+    h.sum_into(&mut out);
+    assert_eq!(sum, &out[..h.size()], "sum_into differs from sum");
 
     let a = h.sum(None);
     assert_eq!(sum, a, "double sum produces different outputs");
 
     h.reset();
-    let _ = h.write(data).unwrap();
+    h.update(data);
 
     let a = h.sum(None);
     assert_eq!(sum, a, "sum after reset produces different outputs");
 
     h.reset();
-    let _ = h.write(&data[..2]).unwrap();
-    let _ = h.write(&data[2..]).unwrap();
+    h.update(&data[..2]);
+    h.update(&data[2..]);
     let a = h.sum(None);
     assert_eq!(sum, a, "sum with partial write produces different outputs");
 }
 
+fn test_integrity128<H>(h: H)
+where
+    H: Hash128,
+{
+    let mut h = h;
+
+    let data = &[b'1', b'2', 3, 4, 5];
+
+    h.update(data);
+    let sum = h.sum(None);
+
+    let sum128 = h.sum128().to_be_bytes();
+    assert_eq!(sum, sum128, "sum != sum128");
+
+    h.reset();
+    test_integrity(h);
+}
+
 fn test_integrity32<H>(h: H)
 where
     H: Hash32,
@@ -113,7 +324,7 @@ where
 
     let data = &[b'1', b'2', 3, 4, 5];
 
-    let _ = h.write(data).unwrap();
+    h.update(data);
     let sum = h.sum(None);
 
     let sum32 = h.sum32().to_be_bytes();
@@ -131,7 +342,7 @@ where
 
     let data = &[b'1', b'2', 3, 4, 5];
 
-    let _ = h.write(data).unwrap();
+    h.update(data);
     let sum = h.sum(None);
 
     let sum64 = h.sum64().to_be_bytes();
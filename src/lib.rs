@@ -1,16 +1,86 @@
 //! Crate hash provides interfaces for hash functions.
 //!
+//! # Features
+//! - `std` (default): implements [`std::io::Write`] for every hash and detects CPU features at runtime. Without it,
+//!   the crate is `no_std` and only needs `alloc`, and hardware acceleration is picked from the compile-time target
+//!   features.
+//! - `maphash` (default): enables the [maphash] module, which requires `std`.
+//!
+//! # Migrating from 0.4
+//! Constructors such as `crc32::new` or `fnv::new64a` return concrete digest types instead of `impl Trait`, so
+//! callers bring the traits whose methods they use into scope, e.g. `use hash::{Hash, Hash32};`.
+//!
+//! [Hash] no longer has `std::io::Write` as a supertrait, so that its definition is the same with or without the
+//! `std` feature. Generic code writing into an `H: Hash` calls [update](Hash::update), or bounds `H` by
+//! `std::io::Write` as well, which every hash of this crate implements when `std` is enabled.
+//!
+//! The FNV hashes and [maphash::Hash] implement [`core::hash::Hasher`] too. Where both it
+//! and `std::io::Write` are in scope, `h.write(..)` is ambiguous and has to be spelled `Write::write(&mut h, ..)`.
+//!
+//! Implementors of [Hash] and its fixed-size variants need to
+//! - implement [update](Hash::update), which `std::io::Write::write` may forward to;
+//! - implement [sum_into](Hash::sum_into) rather than [sum](Hash::sum), which is now provided on top of it;
+//! - take `&self` instead of `&mut self` in [sum_into](Hash::sum_into), [sum16](Hash16::sum16),
+//!   [sum32](Hash32::sum32), [sum64](Hash64::sum64) and [sum128](Hash128::sum128), since reading the hash never changes its state.
+//!
 
-use std::io::Write;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// impl_write implements [`std::io::Write`] for hashes by forwarding to [Hash::update], when `std` is enabled.
+macro_rules! impl_write {
+    ($($t:ty),*) => {
+        $(
+            #[cfg(feature = "std")]
+            impl std::io::Write for $t {
+                /// It always writes all of buf and never fails.
+                fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                    crate::Hash::update(self, buf);
+                    Ok(buf.len())
+                }
+
+                fn flush(&mut self) -> std::io::Result<()> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_write;
 
 /// Hash is the common interface implemented by all hash functions.
 ///
-/// [`std::io::Write`] trait requirement adds more data to the running hash.
-/// It never returns an error.
-pub trait Hash: Write {
+/// [update](Hash::update) adds more data to the running hash. When `std` is enabled, the hashes of this crate also
+/// implement [`std::io::Write`] by forwarding to it, and never return an error. The trait itself doesn't depend on
+/// `std`, so implementing it works the same with or without the feature.
+///
+/// Reading the current hash never changes its state, so [sum](Hash::sum), [sum_into](Hash::sum_into) and the sums of
+/// the fixed-size variants take `&self`, letting a hash be read through shared references.
+pub trait Hash {
+    /// update adds the bytes in p to the running hash.
+    fn update(&mut self, p: &[u8]);
+
     /// sum appends the current hash to b and returns the resulting slice.
     /// It does not change the underlying hash state.
-    fn sum(&mut self, b: Option<Vec<u8>>) -> Vec<u8>;
+    fn sum(&self, b: Option<Vec<u8>>) -> Vec<u8> {
+        let mut b = b.unwrap_or_default();
+        let n = b.len();
+        b.resize(n + self.size(), 0);
+        self.sum_into(&mut b[n..]);
+        b
+    }
+
+    /// sum_into writes the current hash into the first [size](Hash::size) bytes of out, in the same layout as
+    /// [sum](Hash::sum) but without allocating. It does not change the underlying hash state.
+    ///
+    /// # Panics
+    /// It panics if out is shorter than [size](Hash::size).
+    fn sum_into(&self, out: &mut [u8]);
 
     /// reset resets the Hash to its initial state.
     fn reset(&mut self);
@@ -25,18 +95,81 @@ pub trait Hash: Write {
     fn block_size(&self) -> isize;
 }
 
+/// Hash8 is the common interface implemented by all 8-bit hash functions.
+pub trait Hash8: Hash {
+    fn sum8(&self) -> u8;
+}
+
+/// Hash16 is the common interface implemented by all 16-bit hash functions.
+pub trait Hash16: Hash {
+    fn sum16(&self) -> u16;
+}
+
 /// Hash32 is the common interface implemented by all 32-bit hash functions.
 pub trait Hash32: Hash {
-    fn sum32(&mut self) -> u32;
+    fn sum32(&self) -> u32;
 }
 
 /// Hash64 is the common interface implemented by all 64-bit hash functions.
 pub trait Hash64: Hash {
-    fn sum64(&mut self) -> u64;
+    fn sum64(&self) -> u64;
+}
+
+/// Hash128 is the common interface implemented by all 128-bit hash functions.
+pub trait Hash128: Hash {
+    fn sum128(&self) -> u128;
+}
+
+/// BinaryMarshaler is the interface implemented by an object that can marshal itself into a binary form.
+///
+/// It mirrors Go's `encoding.BinaryMarshaler`, so the running state of a hash can be saved and restored later.
+pub trait BinaryMarshaler {
+    /// marshal_binary encodes the receiver into a binary form and returns the result.
+    fn marshal_binary(&self) -> Result<Vec<u8>, Error>;
+}
+
+/// BinaryUnmarshaler is the interface implemented by an object that can unmarshal a binary representation of
+/// itself.
+///
+/// unmarshal_binary must be able to decode the form generated by [marshal_binary][BinaryMarshaler::marshal_binary].
+pub trait BinaryUnmarshaler {
+    /// unmarshal_binary restores the state of the receiver from data.
+    fn unmarshal_binary(&mut self, data: &[u8]) -> Result<(), Error>;
 }
 
+/// Error enumerates errors reported when restoring a hash state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The state doesn't start with the magic prefix of the hash.
+    InvalidStateIdentifier,
+    /// The state has a wrong length.
+    InvalidStateSize,
+    /// The state was produced by a hash using another table.
+    TableMismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::InvalidStateIdentifier => "invalid hash state identifier",
+            Self::InvalidStateSize => "invalid hash state size",
+            Self::TableMismatch => "tables do not match",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
 pub mod adler32;
 
+pub mod crc;
+
+pub mod crc16;
+
+pub mod crc8;
+
 pub mod crc32;
 
 pub mod crc64;
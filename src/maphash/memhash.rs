@@ -21,8 +21,15 @@ const M3: usize = 0x8ebc6af09c88c6e3;
 const M4: usize = 0x589965cc75374cc3;
 const M5: usize = 0x1d8e4e27c47d124f;
 
+/// hash_key returns the per-process random key mixed into the hashes of random seeds.
+pub fn hash_key() -> usize {
+    HASH_KEY[0]
+}
+
 /// ref: https://github.com/golang/go/blob/go1.19.4/src/runtime/hash64.go#L25
-pub unsafe fn sum<T>(addr: &T, seed: usize, s: usize) -> usize
+///
+/// key plays the role of the runtime's `hashkey[0]`.
+pub unsafe fn sum<T>(addr: &T, seed: usize, key: usize, s: usize) -> usize
 where
     T: ?Sized,
 {
@@ -30,13 +37,13 @@ where
 
     //let (mut a, mut b) = (0usize, 0usize);
 
-    let mut seed = seed ^ (HASH_KEY[0] ^ M1);
+    let mut seed = seed ^ (key ^ M1);
     let (a, b) = match s {
         0 => return seed,
         1..=3 => {
             let mut a = *p as usize;
-            a |= ((*p.offset((s >> 1) as isize)) as usize) << 8;
-            a |= (*(p.offset((s - 1) as isize)) as usize) << 16;
+            a |= ((*p.add(s >> 1)) as usize) << 8;
+            a |= (*(p.add(s - 1)) as usize) << 16;
             (a, 0)
         }
         4 => {
@@ -45,7 +52,7 @@ where
         }
         5..=7 => {
             let a = r4(p);
-            let b = r4(p.offset((s - 4) as isize));
+            let b = r4(p.add(s - 4));
             (a, b)
         }
         8 => {
@@ -54,29 +61,29 @@ where
         }
         9..=16 => {
             let a = r8(p);
-            let b = r8(p.offset((s - 4) as isize));
+            let b = r8(p.add(s - 8));
             (a, b)
         }
         _ => {
-            let mut l = s as isize;
+            let mut l = s;
             if l > 48 {
                 let (mut s1, mut s2) = (seed, seed);
                 while l > 48 {
-                    seed = mix(r8(p) ^ M2, r8(p.offset(8)) ^ seed);
-                    s1 = mix(r8(p.offset(16)) ^ M3, r8(p.offset(24)) ^ s1);
-                    s2 = mix(r8(p.offset(32)) ^ M4, r8(p.offset(40)) ^ s2);
-                    p = p.offset(48);
+                    seed = mix(r8(p) ^ M2, r8(p.add(8)) ^ seed);
+                    s1 = mix(r8(p.add(16)) ^ M3, r8(p.add(24)) ^ s1);
+                    s2 = mix(r8(p.add(32)) ^ M4, r8(p.add(40)) ^ s2);
+                    p = p.add(48);
                     l -= 48;
                 }
                 seed ^= s1 ^ s2;
             }
             while l > 16 {
-                seed = mix(r8(p) ^ M2, r8(p.offset(8)) ^ seed);
-                p = p.offset(16);
+                seed = mix(r8(p) ^ M2, r8(p.add(8)) ^ seed);
+                p = p.add(16);
                 l -= 16;
             }
-            let a = r8(p.offset(l - 16));
-            let b = r8(p.offset(l - 8));
+            let a = r8(p.add(l).sub(16));
+            let b = r8(p.add(l).sub(8));
             (a, b)
         }
     };
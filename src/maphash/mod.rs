@@ -8,7 +8,26 @@
 //! The hash functions are not cryptographically secure.
 //! (See crypto/sha256 and crypto/sha512 for cryptographic use.)
 //!
+//! For hashing a single byte sequence or string, [bytes] and [string] avoid the buffering done by
+//! [Hash][struct@Hash]. Values implementing [`core::hash::Hash`] are hashed by [hash_value] and
+//! [write_value][Hash::write_value].
+//!
+//! [Hash][struct@Hash] implements [`core::hash::Hasher`], and [RandomState] builds Hashes for a
+//! `HashMap` or `HashSet`, giving each map its own [Seed]:
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use hash::maphash::RandomState;
+//!
+//! let mut m = HashMap::with_hasher(RandomState::new());
+//! m.insert("hello", 1);
+//! assert_eq!(Some(&1), m.get("hello"));
+//! ```
+//!
 
+use core::fmt;
+use core::hash::{BuildHasher, Hasher};
 use std::io::{self, Write};
 use std::mem;
 
@@ -16,6 +35,9 @@ use crate::Hash64;
 
 const BUF_SIZE: usize = 128;
 
+/// FIXED_HASH_KEY replaces the per-process random hash key for seeds made by [Seed::from_u64_unstable].
+const FIXED_HASH_KEY: usize = 0;
+
 /// A Hash computes a seeded hash of a byte sequence.
 ///
 /// The zero Hash is a valid Hash ready to use.
@@ -60,19 +82,36 @@ pub struct Hash {
 /// If two Hashes use different Seeds, they are very likely to compute
 /// distinct hash values for any given input.
 ///
-/// A Seed must be initialized by calling [make_seed].
-/// The zero seed is uninitialized and not valid for use with [Hash][struct@Hash]'s
-/// [set_seed][Hash::set_seed] method.
+/// A Seed is made by [make_seed], or by [from_u64_unstable][Self::from_u64_unstable]
+/// for a deterministic one. Unlike in Go, there is no uninitialized zero Seed:
+/// random seeds are never zero, and the deterministic one of 0 is as valid as any other.
 ///
 /// Each Seed value is local to a single process and cannot be serialized
-/// or otherwise recreated in a different process.
+/// or otherwise recreated in a different process, except for the deterministic
+/// ones made by [from_u64_unstable][Self::from_u64_unstable].
 #[derive(Clone, Copy)]
-pub struct Seed(u64);
+pub struct Seed {
+    s: u64,
+    key: usize,
+}
+
+/// RandomState is a [`BuildHasher`] building [Hash][struct@Hash]es which all use the same [Seed].
+///
+/// It plays the role of [`std::collections::hash_map::RandomState`]: each RandomState made by
+/// [new][Self::new] or [default][Self::default] carries its own random seed, so different maps hash
+/// differently, while all the hashes built for one map agree.
+#[derive(Clone)]
+pub struct RandomState {
+    seed: Seed,
+}
 
 impl Hash {
     pub fn new() -> Self {
-        let seed = Seed::new();
+        Self::with_seed(Seed::new())
+    }
 
+    /// with_seed returns a new Hash using seed, as if by [set_seed][Self::set_seed].
+    pub fn with_seed(seed: Seed) -> Self {
         Self {
             seed,
             state: seed,
@@ -117,24 +156,61 @@ impl Hash {
     where
         S: AsRef<str>,
     {
-        self.write(s.as_ref().as_bytes())
+        Write::write(self, s.as_ref().as_bytes())
+    }
+
+    /// write_value adds v to the sequence of bytes hashed by h, as written by v's [`core::hash::Hash`]
+    /// implementation. See [hash_value] for how values map to bytes.
+    pub fn write_value<T>(&mut self, v: &T)
+    where
+        T: core::hash::Hash + ?Sized,
+    {
+        v.hash(self);
+    }
+}
+
+impl Default for Hash {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl crate::Hash for Hash {
-    /// sum appends the hash's current 64-bit value to b.
+    /// update adds p to the sequence of bytes hashed by h.
+    fn update(&mut self, p: &[u8]) {
+        let mut b = p;
+
+        // Deal with bytes left over in h.buf.
+        // h.n <= bufSize is always true.
+        // Checking it is ~free and it lets the compiler eliminate a bounds check.
+        if self.n > 0 && self.n <= BUF_SIZE {
+            let k = copy(&mut self.buf[self.n..], b);
+            self.n += k;
+            if self.n < BUF_SIZE {
+                // Copied the entirety of b to h.buf.
+                return;
+            }
+            b = &b[k..];
+            let _ = self.flush();
+            // No need to set h.n = 0 here; it happens just before exit.
+        }
+
+        while b.len() > BUF_SIZE {
+            self.state.s = rthash(b, BUF_SIZE, self.state);
+            b = &b[BUF_SIZE..];
+        }
+
+        // copy the tail
+        let _ = copy(&mut self.buf, b);
+        self.n = b.len();
+    }
+
+    /// sum_into writes the hash's current 64-bit value to out, in little-endian byte order.
     /// It exists for implementing [Hash][crate::Hash].
     /// For direct calls, it is more efficient to use [sum64](#method.sum64).
-    fn sum(&mut self, b: Option<Vec<u8>>) -> Vec<u8> {
+    fn sum_into(&self, out: &mut [u8]) {
         let s = self.sum64().to_le_bytes();
-        match b {
-            Some(v) => {
-                let mut v = v;
-                v.extend_from_slice(&s);
-                v
-            }
-            None => s.to_vec(),
-        }
+        out[..s.len()].copy_from_slice(&s);
     }
 
     /// reset discards all bytes added to h. (The seed remains the same.)
@@ -162,8 +238,10 @@ impl Hash64 for Hash {
     /// All bits of the sum64 result are close to uniformly and
     /// independently distributed, so it can be safely reduced
     /// by using bit masking, shifting, or modular arithmetic.
-    fn sum64(&mut self) -> u64 {
-        rthash(self.buf.as_ref(), self.n, self.state.0)
+    ///
+    /// It computes the value from the seed and the buffered bytes without flushing them, so h is left untouched.
+    fn sum64(&self) -> u64 {
+        rthash(self.buf.as_ref(), self.n, self.state)
     }
 }
 
@@ -171,44 +249,70 @@ impl Write for Hash {
     /// write adds b to the sequence of bytes hashed by h.
     /// It always writes all of b and never fails; the count and error result are for implementing [std::io::Write].
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let mut b = buf;
-        let size = b.len();
-
-        // Deal with bytes left over in h.buf.
-        // h.n <= bufSize is always true.
-        // Checking it is ~free and it lets the compiler eliminate a bounds check.
-        if self.n > 0 && self.n <= BUF_SIZE {
-            let k = copy(&mut self.buf[self.n..], b);
-            self.n += k;
-            if self.n < BUF_SIZE {
-                // Copied the entirety of b to h.buf.
-                return Ok(size);
-            }
-            b = &b[k..];
-            let _ = self.flush();
-            // No need to set h.n = 0 here; it happens just before exit.
-        }
-
-        while b.len() > BUF_SIZE {
-            self.state.0 = rthash(b, BUF_SIZE, self.state.0);
-            b = &b[BUF_SIZE..];
-        }
-
-        // copy the tail
-        let _ = copy(&mut self.buf, b);
-        self.n = b.len();
-
-        Ok(size)
+        crate::Hash::update(self, buf);
+        Ok(buf.len())
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
         // todo: determine if enforce self.n==self.buf.len()
-        self.state.0 = rthash(self.buf.as_ref(), self.n, self.state.0);
+        self.state.s = rthash(self.buf.as_ref(), self.n, self.state);
         self.n = 0;
         Ok(())
     }
 }
 
+impl Hasher for Hash {
+    /// finish returns [sum64](#method.sum64).
+    fn finish(&self) -> u64 {
+        self.sum64()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        crate::Hash::update(self, bytes);
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        let _ = self.write_byte(i);
+    }
+}
+
+impl RandomState {
+    /// new returns a RandomState with a new random seed.
+    pub fn new() -> Self {
+        Self::with_seed(Seed::new())
+    }
+
+    /// with_seed returns a RandomState whose hashes all use seed.
+    pub fn with_seed(seed: Seed) -> Self {
+        Self { seed }
+    }
+
+    /// seed returns the seed of the hashes built by s.
+    pub fn seed(&self) -> &Seed {
+        &self.seed
+    }
+}
+
+impl Default for RandomState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for RandomState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RandomState").finish_non_exhaustive()
+    }
+}
+
+impl BuildHasher for RandomState {
+    type Hasher = Hash;
+
+    fn build_hasher(&self) -> Hash {
+        Hash::with_seed(self.seed)
+    }
+}
+
 impl Seed {
     /// new makes a random seed.
     pub fn new() -> Seed {
@@ -216,16 +320,120 @@ impl Seed {
         while s == 0 {
             s = rand_u64();
         }
-        Self(s)
+        Self {
+            s,
+            key: memhash::hash_key(),
+        }
+    }
+
+    /// from_u64_unstable returns the seed selected by v, which is the same in every process. Every v is valid,
+    /// including 0.
+    ///
+    /// Unlike [new][Self::new], it doesn't mix in the per-process random hash key, so anyone knowing v can
+    /// predict the hash values and craft colliding inputs: such seeds are weaker against HashDoS and
+    /// should only back data not controlled by an adversary, e.g. in snapshot tests or for sharding.
+    ///
+    /// The hash values are reproducible across processes built from the same version of this crate for
+    /// targets of the same endianness, but may change in any release, hence the `_unstable` suffix.
+    pub const fn from_u64_unstable(v: u64) -> Seed {
+        Self {
+            s: v,
+            key: FIXED_HASH_KEY,
+        }
     }
 }
 
+impl Default for Seed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// bytes returns the hash of b with the given seed.
+///
+/// bytes is equivalent to, but more convenient and efficient than:
+///
+/// ```no_test
+/// let mut h = Hash::with_seed(seed);
+/// h.write(b);
+/// h.sum64()
+/// ```
+pub fn bytes(seed: Seed, b: &[u8]) -> u64 {
+    let mut state = seed;
+    let mut b = b;
+    while b.len() > BUF_SIZE {
+        state.s = rthash(b, BUF_SIZE, state);
+        b = &b[BUF_SIZE..];
+    }
+
+    rthash(b, b.len(), state)
+}
+
+/// string returns the hash of s with the given seed.
+///
+/// string is equivalent to, but more convenient and efficient than:
+///
+/// ```no_test
+/// let mut h = Hash::with_seed(seed);
+/// h.write_string(s);
+/// h.sum64()
+/// ```
+pub fn string<S>(seed: Seed, s: S) -> u64
+where
+    S: AsRef<str>,
+{
+    bytes(seed, s.as_ref().as_bytes())
+}
+
+/// hash_value returns the hash of v with the given seed, i.e. the hash of the bytes written by v's
+/// [`core::hash::Hash`] implementation.
+///
+/// Values that compare equal under [`Eq`] have the same hash, as required of `core::hash::Hash`.
+/// The bytes written follow that implementation rather than v's memory layout:
+/// - integers are written in native-endian byte order, so hashing `&n` equals [bytes] of `n.to_ne_bytes()`;
+/// - `str`, slices and collections add a terminator or a length prefix, so hashing `"foo"` differs from
+///   [string] of `"foo"`.
+///
+/// `f32` and `f64` don't implement `core::hash::Hash`, because `-0.0 == 0.0` while their bits differ and
+/// `NaN != NaN`. A type hashing floats should write `0.0` for both zeros and one canonical NaN bit pattern,
+/// in line with its `Eq`:
+///
+/// ```
+/// use core::hash::{Hash, Hasher};
+///
+/// use hash::maphash;
+///
+/// struct Key(f64);
+///
+/// impl Hash for Key {
+///     fn hash<H: Hasher>(&self, h: &mut H) {
+///         let v = if self.0 == 0.0 { 0.0 } else if self.0.is_nan() { f64::NAN } else { self.0 };
+///         v.to_bits().hash(h);
+///     }
+/// }
+///
+/// let seed = maphash::make_seed();
+/// assert_eq!(maphash::hash_value(seed, &Key(0.0)), maphash::hash_value(seed, &Key(-0.0)));
+/// assert_eq!(
+///     maphash::hash_value(seed, &Key(1.5)),
+///     maphash::bytes(seed, &1.5f64.to_bits().to_ne_bytes())
+/// );
+/// ```
+pub fn hash_value<T>(seed: Seed, v: &T) -> u64
+where
+    T: core::hash::Hash + ?Sized,
+{
+    let mut h = Hash::with_seed(seed);
+    h.write_value(v);
+    h.sum64()
+}
+
 /// make_seed returns a new random seed.
 pub fn make_seed() -> Seed {
     Seed::new()
 }
 
-pub(self) fn copy(dst: &mut [u8], src: &[u8]) -> usize {
+fn copy(dst: &mut [u8], src: &[u8]) -> usize {
     let n = dst.len().min(src.len());
 
     dst[..n].copy_from_slice(&src[..n]);
@@ -240,18 +448,19 @@ fn rand_u64() -> u64 {
     u64::from_be_bytes(b)
 }
 
-fn rthash(ptr: &[u8], len: usize, seed: u64) -> u64 {
+fn rthash(ptr: &[u8], len: usize, seed: Seed) -> u64 {
+    let Seed { s: seed, key } = seed;
     if len == 0 {
         return seed;
     }
 
     if mem::size_of::<usize>() == 8 {
-        return unsafe { memhash::sum(ptr, seed as usize, len) as u64 };
+        return unsafe { memhash::sum(ptr, seed as usize, key, len) as u64 };
     }
 
     let (lo, hi) = unsafe {
-        let lo = memhash::sum(ptr, seed as usize, len);
-        let hi = memhash::sum(ptr, (seed >> 32) as usize, len);
+        let lo = memhash::sum(ptr, seed as usize, key, len);
+        let hi = memhash::sum(ptr, (seed >> 32) as usize, key, len);
 
         (lo, hi)
     };
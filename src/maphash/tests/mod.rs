@@ -1,8 +1,9 @@
-use std::{collections::HashSet, io::Write};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 
 use crate::{Hash, Hash64};
 
-use super::Hash as MapHash;
+use super::{Hash as MapHash, RandomState};
 
 #[test]
 fn hash_bytes_vs_string() {
@@ -26,6 +27,74 @@ fn hash_bytes_vs_string() {
     );
 }
 
+#[test]
+fn bytes_vs_hash() {
+    let seed = super::make_seed();
+    let b: Vec<u8> = (0..300).map(|i| (i * 7 + 3) as u8).collect();
+
+    for n in 0..b.len() {
+        let mut h = MapHash::with_seed(seed);
+        h.write_all(&b[..n]).unwrap();
+        assert_eq!(h.sum64(), super::bytes(seed, &b[..n]), "bytes({})", n);
+    }
+
+    let s = "hello, world";
+    let mut h = MapHash::with_seed(seed);
+    h.write_string(s).unwrap();
+    assert_eq!(h.sum64(), super::string(seed, s), "string");
+}
+
+#[test]
+fn bytes_every_byte_counts() {
+    let seed = super::make_seed();
+
+    for n in 1..=48 {
+        let b = vec![0u8; n];
+        let x = super::bytes(seed, &b);
+        for i in 0..n {
+            let mut c = b.clone();
+            c[i] = 1;
+            assert_ne!(x, super::bytes(seed, &c), "len {}: byte {} ignored", n, i);
+        }
+    }
+}
+
+#[test]
+fn hash_value() {
+    #[derive(Hash)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let seed = super::make_seed();
+
+    assert_eq!(
+        super::bytes(seed, &42u64.to_ne_bytes()),
+        super::hash_value(seed, &42u64),
+        "integer differs from its bytes"
+    );
+    assert_eq!(
+        super::hash_value(seed, &Point { x: 1, y: 2 }),
+        super::hash_value(seed, &Point { x: 1, y: 2 }),
+        "equal values hash differently"
+    );
+    assert_ne!(
+        super::hash_value(seed, &Point { x: 1, y: 2 }),
+        super::hash_value(seed, &Point { x: 2, y: 1 }),
+        "distinct values hash the same"
+    );
+
+    let mut h = MapHash::with_seed(seed);
+    h.write_value(&Point { x: 1, y: 2 });
+    h.write_value("foo");
+    assert_eq!(
+        super::hash_value(seed, &(Point { x: 1, y: 2 }, "foo")),
+        h.sum64(),
+        "write_value differs from hash_value"
+    );
+}
+
 #[test]
 fn hash_grouping() {
     let b = {
@@ -46,14 +115,14 @@ fn hash_grouping() {
     for h in hh.iter_mut().skip(1) {
         h.set_seed(seed0);
     }
-    hh[0].write(b.as_bytes()).unwrap();
+    hh[0].write_all(b.as_bytes()).unwrap();
     hh[1].write_string(&b).unwrap();
 
     let must_write_byte = |h: &mut MapHash, b: u8| {
         h.write_byte(b).expect("write_byte");
     };
     let must_write_single_byte = |h: &mut MapHash, b: u8| {
-        h.write(&[b]).expect("write_byte");
+        h.write_all(&[b]).expect("write_byte");
     };
     let must_write_string_single_byte = |h: &mut MapHash, b: u8| {
         let mut s = String::with_capacity(1);
@@ -122,17 +191,90 @@ fn repeat() {
     assert_eq!(sum1, sum3, "different sum on the same seed");
 }
 
+#[test]
+fn sum_into() {
+    let mut h = MapHash::new();
+    h.write_string("testing").unwrap();
+
+    let mut out = [0u8; 8];
+    h.sum_into(&mut out);
+    assert_eq!(h.sum64().to_le_bytes(), out, "sum_into differs from sum64");
+    assert_eq!(h.sum(None), out, "sum_into differs from sum");
+}
+
+#[test]
+fn sum_shared() {
+    let mut h = MapHash::new();
+    h.write_string("testing").unwrap();
+    let expect = h.sum64();
+
+    // reads don't need exclusive access, so the hash can be shared between threads without locking.
+    let h = std::sync::Arc::new(h);
+    let h2 = std::sync::Arc::clone(&h);
+    let got = std::thread::spawn(move || h2.sum64()).join().unwrap();
+    assert_eq!(expect, got, "sum64 through Arc");
+    assert_eq!(
+        expect.to_le_bytes().to_vec(),
+        h.sum(None),
+        "sum through Arc"
+    );
+}
+
+#[test]
+fn hasher() {
+    use std::hash::Hasher;
+
+    let mut h1 = MapHash::new();
+    let mut h2 = MapHash::with_seed(*h1.seed());
+
+    h1.write_string("foo").unwrap();
+    h1.write_byte(b'!').unwrap();
+    Hasher::write(&mut h2, b"foo");
+    h2.write_u8(b'!');
+
+    assert_eq!(h1.sum64(), h2.finish(), "finish differs from sum64");
+}
+
+#[test]
+fn random_state() {
+    use std::hash::{BuildHasher, Hash as _, Hasher};
+
+    let s = RandomState::new();
+    let hash_one = |v: &str| {
+        let mut h = s.build_hasher();
+        v.hash(&mut h);
+        h.finish()
+    };
+    let (x, y) = (hash_one("foo"), hash_one("foo"));
+    assert_eq!(x, y, "hashes built by one state don't match");
+
+    let mut h = MapHash::new();
+    h.set_seed(*s.seed());
+    "foo".hash(&mut h);
+    assert_eq!(
+        x,
+        h.finish(),
+        "hash built by state differs from seeded hash"
+    );
+
+    let mut m = HashMap::with_hasher(RandomState::new());
+    m.insert("hello", 1);
+    m.insert("world", 2);
+    assert_eq!(Some(&1), m.get("hello"));
+    assert_eq!(None, m.get("foo"));
+}
+
 #[test]
 fn seed_from_flush() {
     let b = [0u8; 65];
 
     let mut h1 = MapHash::new();
-    h1.write(&b).unwrap();
+    h1.write_all(&b).unwrap();
     let x = h1.sum64();
 
     let mut h2 = MapHash::new();
     h2.set_seed(*h1.seed());
-    h2.write(&b).unwrap();
+    h2.write_all(&b).unwrap();
     let y = h2.sum64();
 
     assert_eq!(x, y, "hashes don't match");
@@ -170,6 +312,63 @@ fn seed_from_sum64() {
     // @note: SeedFromSeed has been demo by this test too.
 }
 
+#[test]
+fn seed_from_u64_unstable() {
+    let s1 = super::Seed::from_u64_unstable(1);
+    let s2 = super::Seed::from_u64_unstable(2);
+
+    let x = super::string(s1, "foo");
+    assert_eq!(
+        x,
+        super::string(super::Seed::from_u64_unstable(1), "foo"),
+        "hashes don't match"
+    );
+    assert_ne!(x, super::string(s2, "foo"), "distinct seeds hash the same");
+
+    let mut h = MapHash::with_seed(s1);
+    h.write_string("foo").unwrap();
+    assert_eq!(x, h.sum64(), "hash differs from string");
+}
+
+#[test]
+fn seed_from_u64_unstable_zero() {
+    let s0 = super::Seed::from_u64_unstable(0);
+    let s1 = super::Seed::from_u64_unstable(1);
+
+    let x = super::string(s0, "foo");
+    assert_eq!(
+        x,
+        super::string(super::Seed::from_u64_unstable(0), "foo"),
+        "hashes don't match"
+    );
+    assert_ne!(x, super::string(s1, "foo"), "seeds 0 and 1 hash the same");
+    assert_ne!(x, super::string(s0, "bar"), "distinct inputs hash the same");
+
+    let mut h = MapHash::with_seed(s0);
+    h.write_string("foo").unwrap();
+    assert_eq!(x, h.sum64(), "hash differs from string");
+}
+
+#[test]
+#[cfg(target_endian = "little")]
+fn seed_from_u64_unstable_golden() {
+    // The values don't depend on the process, so they can be pinned.
+    let seed = super::Seed::from_u64_unstable(0x0123456789abcdef);
+    let b: Vec<u8> = (0..=255).collect();
+
+    let expect = [
+        (0, 0x0123456789abcdef),
+        (3, 0x3903401cc854a6bb),
+        (8, 0x0d73c0a42b3432ce),
+        (16, 0xfa1856a06d546233),
+        (48, 0x338a54845750efa7),
+        (200, 0xef7402ba2a0b7a6c),
+    ];
+    for (n, want) in expect {
+        assert_eq!(want, super::bytes(seed, &b[..n]), "bytes({})", n);
+    }
+}
+
 #[test]
 fn seeded_hash() {
     let s = super::make_seed();
@@ -191,7 +390,7 @@ fn unseeded_hash() {
 
     let expect = 1000usize;
     for _i in 0..expect {
-        let mut h = MapHash::new();
+        let h = MapHash::new();
         m.insert(h.sum64());
     }
 
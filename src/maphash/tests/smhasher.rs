@@ -191,7 +191,7 @@ impl MyHashSet {
 fn bytes_hash(b: &[u8]) -> u64 {
     let mut h = Hash::new();
     h.set_seed(*FIXED_SEED);
-    h.write(b).unwrap();
+    h.write_all(b).unwrap();
     h.sum64()
 }
 